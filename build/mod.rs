@@ -30,7 +30,9 @@ fn main() {
     let out_dir = PathBuf::from(out_dir);
 
     if cfg!(feature = "_skip_linking") {
-        let version = if cfg!(feature = "ruby_2_6") {
+        let version = if cfg!(feature = "ruby_2_7") {
+            "2.7"
+        } else if cfg!(feature = "ruby_2_6") {
             "2.6"
         } else {
             "unknown"
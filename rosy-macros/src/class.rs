@@ -0,0 +1,297 @@
+use proc_macro2::{Delimiter, Group, TokenStream, TokenTree};
+use quote::{format_ident, quote, ToTokens};
+use syn::{
+    spanned::Spanned,
+    Attribute,
+    Error,
+    FnArg,
+    ImplItem,
+    ImplItemMethod,
+    ItemImpl,
+    Lit,
+    LitStr,
+    Meta,
+    NestedMeta,
+    Result,
+    ReturnType,
+    Type,
+    Visibility,
+};
+
+// The maximum number of arguments that a method can take via `MethodFn`
+const MAX_ARGS: usize = 15;
+
+// How a method is exposed in Ruby
+enum Kind {
+    // `pub fn new(...)`, which becomes `initialize`
+    New,
+    // `pub fn f(...)`, which becomes a singleton method on the class
+    Singleton,
+    // `pub fn f(&self, ...)`
+    Ref,
+    // `pub fn f(&mut self, ...)`
+    Mut,
+}
+
+struct Method {
+    kind: Kind,
+    ident: syn::Ident,
+    name: LitStr,
+    args: Vec<Type>,
+}
+
+// Options given via `#[rosy(...)]` on a method, which are removed afterward
+struct MethodOptions {
+    name: Option<LitStr>,
+    skip: bool,
+}
+
+impl MethodOptions {
+    fn take(attrs: &mut Vec<Attribute>) -> Result<Self> {
+        let mut options = MethodOptions { name: None, skip: false };
+        let mut metas = Vec::new();
+
+        for attr in attrs.iter().filter(|attr| attr.path.is_ident("rosy")) {
+            match attr.parse_meta()? {
+                Meta::List(list) => metas.extend(list.nested),
+                meta => return Err(Error::new(meta.span(), "expected `#[rosy(...)]`")),
+            }
+        }
+        attrs.retain(|attr| !attr.path.is_ident("rosy"));
+
+        for meta in metas {
+            match meta {
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("skip") => {
+                    options.skip = true;
+                },
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("name") => {
+                    match nv.lit {
+                        Lit::Str(name) => options.name = Some(name),
+                        lit => return Err(Error::new(lit.span(), "expected a string")),
+                    }
+                },
+                meta => return Err(Error::new(meta.span(), "unknown `rosy` method option")),
+            }
+        }
+        Ok(options)
+    }
+}
+
+impl Method {
+    fn parse(method: &mut ImplItemMethod, self_ty: &Type) -> Result<Option<Self>> {
+        let options = MethodOptions::take(&mut method.attrs)?;
+        if options.skip {
+            return Ok(None);
+        }
+        match method.vis {
+            Visibility::Public(_) => {},
+            _ => return Ok(None),
+        }
+
+        let sig = &method.sig;
+        if let Some(asyncness) = &sig.asyncness {
+            return Err(Error::new(asyncness.span(), "`async` methods are not supported"));
+        }
+        if !sig.generics.params.is_empty() {
+            return Err(Error::new(sig.generics.span(), "generic methods are not supported"));
+        }
+
+        let ident = sig.ident.clone();
+        let mut kind = if ident == "new" { Kind::New } else { Kind::Singleton };
+        let mut args = Vec::new();
+
+        for input in &sig.inputs {
+            match input {
+                FnArg::Receiver(receiver) => {
+                    if receiver.reference.is_none() {
+                        let message = "methods taking `self` by value are not supported";
+                        return Err(Error::new(receiver.span(), message));
+                    }
+                    kind = if receiver.mutability.is_some() { Kind::Mut } else { Kind::Ref };
+                },
+                FnArg::Typed(arg) => {
+                    if let Type::ImplTrait(ty) = &*arg.ty {
+                        let message = "`impl Trait` arguments are not supported";
+                        return Err(Error::new(ty.span(), message));
+                    }
+                    args.push(replace_self(&arg.ty, self_ty)?);
+                },
+            }
+        }
+
+        if args.len() > MAX_ARGS {
+            let message = format!("methods can take at most {} arguments", MAX_ARGS);
+            return Err(Error::new(sig.inputs.span(), message));
+        }
+        if let (Kind::New, ReturnType::Default) = (&kind, &sig.output) {
+            return Err(Error::new(sig.span(), "`new` must return `Self`"));
+        }
+
+        let name = match (&kind, options.name) {
+            (Kind::New, Some(name)) => {
+                let message = "`new` is always defined as `initialize`";
+                return Err(Error::new(name.span(), message));
+            },
+            (Kind::New, None) => LitStr::new("initialize", ident.span()),
+            (_, Some(name)) => name,
+            (_, None) => LitStr::new(&ident.to_string(), ident.span()),
+        };
+        Ok(Some(Method { kind, ident, name, args }))
+    }
+
+    fn define(&self, self_ty: &Type, class_name: &LitStr) -> TokenStream {
+        let Method { ident, name, args, .. } = self;
+
+        let arg_idents: Vec<_> = (0..args.len())
+            .map(|i| format_ident!("__arg{}", i))
+            .collect();
+        let arg_placeholders = args.iter().map(|_| quote!(_));
+
+        let this_ty = match self.kind {
+            Kind::Singleton => quote!(::rosy::Class),
+            Kind::New | Kind::Ref | Kind::Mut => quote!(::rosy::AnyObject),
+        };
+
+        let call = match self.kind {
+            Kind::New => quote! {
+                let __data = ::rosy::mixin::_NewOutput::_into_result(
+                    <#self_ty>::#ident(#(#arg_idents),*)
+                )?;
+                ::rosy::RosyObject::<#self_ty>::_initialize(__this, __data)?;
+                let __output = ();
+            },
+            Kind::Singleton => quote! {
+                let __output = <#self_ty>::#ident(#(#arg_idents),*);
+            },
+            Kind::Ref => quote! {
+                let __this = ::rosy::RosyObject::<#self_ty>::_cast_self(
+                    __this,
+                    #class_name,
+                )?;
+                // If the method raises or is broken out of, the skipped borrow
+                // is released by `_call_method`
+                let __output = {
+                    let __data = __this.borrow()?;
+                    <#self_ty>::#ident(&__data, #(#arg_idents),*)
+                };
+            },
+            Kind::Mut => quote! {
+                let __this = ::rosy::RosyObject::<#self_ty>::_cast_self(
+                    __this,
+                    #class_name,
+                )?;
+                // If the method raises or is broken out of, the skipped borrow
+                // is released by `_call_method`
+                let __output = {
+                    let mut __data = __this.borrow_mut()?;
+                    <#self_ty>::#ident(&mut __data, #(#arg_idents),*)
+                };
+            },
+        };
+
+        let def = match self.kind {
+            Kind::Singleton => quote! {
+                ::rosy::Object::def_singleton_method(__class, #name, _method)?;
+            },
+            Kind::New | Kind::Ref | Kind::Mut => quote! {
+                __class.def_method(#name, _method)?;
+            },
+        };
+
+        quote! { {
+            // Arguments are taken as `AnyObject` and then converted via
+            // `FromRuby`
+            #[allow(unused_variables)]
+            extern "C" fn _method(
+                __this: #this_ty,
+                #(#arg_idents: ::rosy::AnyObject),*
+            ) -> ::rosy::AnyObject {
+                ::rosy::mixin::_call_method(|| {
+                    #(
+                        let #arg_idents: #args =
+                            ::rosy::FromRuby::from_ruby(&#arg_idents)?;
+                    )*
+                    #call
+                    ::rosy::mixin::_MethodOutput::_into_result(__output)
+                })
+            }
+
+            let _method: extern "C" fn(_, #(#arg_placeholders),*) -> _ = _method;
+            #def
+        } }
+    }
+}
+
+// `Self` can't be referred to from within the generated `extern fn` items
+fn replace_self(ty: &Type, self_ty: &Type) -> Result<Type> {
+    fn replace(tokens: TokenStream, self_ty: &Type) -> TokenStream {
+        tokens.into_iter().map(|token| match token {
+            TokenTree::Ident(ident) if ident == "Self" => {
+                let mut group = Group::new(Delimiter::None, self_ty.to_token_stream());
+                group.set_span(ident.span());
+                TokenTree::Group(group)
+            },
+            TokenTree::Group(group) => {
+                let mut new = Group::new(
+                    group.delimiter(),
+                    replace(group.stream(), self_ty),
+                );
+                new.set_span(group.span());
+                TokenTree::Group(new)
+            },
+            token => token,
+        }).collect()
+    }
+    syn::parse2(replace(ty.to_token_stream(), self_ty))
+}
+
+pub fn class(name: LitStr, mut item: ItemImpl) -> Result<TokenStream> {
+    if let Some((_, path, _)) = &item.trait_ {
+        let message = "expected an inherent `impl` block, not a trait `impl`";
+        return Err(Error::new(path.span(), message));
+    }
+    if !item.generics.params.is_empty() {
+        let message = "generic `impl` blocks are not supported";
+        return Err(Error::new(item.generics.span(), message));
+    }
+    if name.value().is_empty() || name.value().contains("::") {
+        let message = "expected a class name like \"Foo\"; use the `parent` of `define` for namespacing";
+        return Err(Error::new(name.span(), message));
+    }
+
+    let self_ty = (*item.self_ty).clone();
+    let mut methods = Vec::new();
+    for impl_item in &mut item.items {
+        if let ImplItem::Method(method) = impl_item {
+            if let Some(method) = Method::parse(method, &self_ty)? {
+                methods.push(method);
+            }
+        }
+    }
+
+    let has_new = methods.iter().any(|method| matches!(method.kind, Kind::New));
+    let definitions = methods.iter().map(|method| method.define(&self_ty, &name));
+    let doc = format!(
+        "Defines the `{}` Ruby class under `parent` along with its methods.",
+        name.value(),
+    );
+
+    Ok(quote! {
+        #item
+
+        impl #self_ty {
+            #[doc = #doc]
+            ///
+            /// If the class already exists, it is reopened.
+            pub fn define(parent: impl ::rosy::Mixin) -> ::rosy::Result<::rosy::Class> {
+                let __class = ::rosy::mixin::_def_rosy_class::<#self_ty>(
+                    parent,
+                    #name,
+                    #has_new,
+                )?;
+                #(#definitions)*
+                ::std::result::Result::Ok(__class)
+            }
+        }
+    })
+}
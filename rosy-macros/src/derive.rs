@@ -0,0 +1,191 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote, quote_spanned};
+use syn::{
+    spanned::Spanned,
+    Attribute,
+    Data,
+    DeriveInput,
+    Error,
+    Fields,
+    Lit,
+    LitStr,
+    Meta,
+    NestedMeta,
+    Result,
+};
+
+// Options given via `#[rosy(...)]` on the type itself
+#[derive(Default)]
+struct TypeOptions {
+    class: Option<LitStr>,
+}
+
+impl TypeOptions {
+    fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut options = Self::default();
+        for meta in rosy_metas(attrs)? {
+            match meta {
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("class") => {
+                    let class = match nv.lit {
+                        Lit::Str(class) => class,
+                        lit => return Err(Error::new(lit.span(), "expected a string")),
+                    };
+                    if class.value().split("::").any(str::is_empty) {
+                        let message = "expected a class path like \"Foo::Bar\"";
+                        return Err(Error::new(class.span(), message));
+                    }
+                    if options.class.replace(class).is_some() {
+                        return Err(Error::new(nv.path.span(), "duplicate `class` option"));
+                    }
+                },
+                meta => return Err(Error::new(meta.span(), "unknown `rosy` option")),
+            }
+        }
+        Ok(options)
+    }
+}
+
+// Returns whether a field is marked with `#[rosy(skip)]`
+fn is_skipped(attrs: &[Attribute]) -> Result<bool> {
+    let mut skip = false;
+    for meta in rosy_metas(attrs)? {
+        match meta {
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("skip") => {
+                skip = true;
+            },
+            meta => return Err(Error::new(meta.span(), "unknown `rosy` field option")),
+        }
+    }
+    Ok(skip)
+}
+
+fn rosy_metas(attrs: &[Attribute]) -> Result<Vec<NestedMeta>> {
+    let mut metas = Vec::new();
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("rosy")) {
+        match attr.parse_meta()? {
+            Meta::List(list) => metas.extend(list.nested),
+            meta => return Err(Error::new(meta.span(), "expected `#[rosy(...)]`")),
+        }
+    }
+    Ok(metas)
+}
+
+// A pattern that binds every field not marked with `#[rosy(skip)]`, along with
+// the bindings
+fn bind_fields(path: TokenStream, fields: &Fields) -> Result<(TokenStream, Vec<TokenStream>)> {
+    let mut bindings = Vec::new();
+    let mut patterns = Vec::new();
+
+    for (i, field) in fields.iter().enumerate() {
+        let binding = if is_skipped(&field.attrs)? {
+            quote!(_)
+        } else {
+            let binding = format_ident!("__field{}", i);
+            bindings.push(quote_spanned!(field.span()=> #binding));
+            quote!(#binding)
+        };
+        patterns.push(match &field.ident {
+            Some(ident) => quote!(#ident: #binding),
+            None => binding,
+        });
+    }
+
+    let pattern = match fields {
+        Fields::Named(_) => quote!(#path { #(#patterns),* }),
+        Fields::Unnamed(_) => quote!(#path ( #(#patterns),* )),
+        Fields::Unit => path,
+    };
+    Ok((pattern, bindings))
+}
+
+pub fn rosy(input: DeriveInput) -> Result<TokenStream> {
+    let options = TypeOptions::parse(&input.attrs)?;
+
+    let arms: Vec<(TokenStream, Vec<TokenStream>)> = match &input.data {
+        Data::Struct(data) => {
+            vec![bind_fields(quote!(Self), &data.fields)?]
+        },
+        Data::Enum(data) => {
+            data.variants.iter().map(|variant| {
+                let ident = &variant.ident;
+                bind_fields(quote!(Self::#ident), &variant.fields)
+            }).collect::<Result<_>>()?
+        },
+        Data::Union(data) => {
+            let message = "`Rosy` cannot be derived for unions";
+            return Err(Error::new(data.union_token.span(), message));
+        },
+    };
+
+    let mark_arms = arms.iter().map(|(pattern, bindings)| quote! {
+        #pattern => { #(::rosy::gc::Mark::mark(#bindings);)* }
+    });
+    let size_arms = arms.iter().map(|(pattern, bindings)| quote! {
+        #pattern => 0 #(+ ::rosy::gc::HeapSize::heap_size(#bindings))*
+    });
+
+    // References to empty enums aren't considered uninhabited
+    let scrutinee = if arms.is_empty() { quote!(*self) } else { quote!(self) };
+
+    // Instances are recognized at runtime by `ID`, which can't differ between
+    // instances of a generic type or lifetimes
+    if let Some(param) = input.generics.lifetimes().next() {
+        let message = "`Rosy` cannot be derived for types with lifetime parameters";
+        return Err(Error::new(param.span(), message));
+    }
+    if let Some(param) = input.generics.type_params().next() {
+        let message = "`Rosy` cannot be derived for types with type parameters";
+        return Err(Error::new(param.span(), message));
+    }
+    if let Some(param) = input.generics.const_params().next() {
+        let message = "`Rosy` cannot be derived for types with const parameters";
+        return Err(Error::new(param.span(), message));
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let class = options.class.map(|class| quote! {
+        #[inline]
+        fn class() -> ::rosy::Class {
+            ::rosy::_class_at_path(#class)
+        }
+    });
+
+    let name = LitStr::new(&ident.to_string(), Span::call_site());
+
+    Ok(quote! {
+        unsafe impl #impl_generics ::rosy::Rosy for #ident #ty_generics #where_clause {
+            const ID: *const ::std::os::raw::c_char = ::std::concat!(
+                ::std::module_path!(), "::", #name, "\0",
+            ).as_ptr() as *const ::std::os::raw::c_char;
+
+            #[inline]
+            fn unique_object_id() -> ::std::option::Option<u128> {
+                ::std::option::Option::Some(::rosy::_unique_object_id(
+                    ::std::any::type_name::<Self>(),
+                ))
+            }
+
+            #class
+
+            // Safe because the `ID` is unique to this non-generic type
+            #[inline]
+            fn cast<A: ::rosy::Object>(
+                obj: A,
+            ) -> ::std::option::Option<::rosy::RosyObject<Self>> {
+                unsafe { ::rosy::RosyObject::cast_by_id(obj) }
+            }
+
+            #[inline]
+            fn mark(&self) {
+                match #scrutinee { #(#mark_arms)* }
+            }
+
+            #[inline]
+            fn size(&self) -> usize {
+                ::std::mem::size_of_val(self) + match #scrutinee { #(#size_arms,)* }
+            }
+        }
+    })
+}
@@ -0,0 +1,37 @@
+//! Procedural macros for [Rosy](https://docs.rs/rosy).
+//!
+//! These are re-exported by Rosy when its `macros` feature is enabled and
+//! should be used through it rather than by depending on this crate directly.
+
+#![deny(missing_docs)]
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput, ItemImpl, LitStr};
+
+mod class;
+mod derive;
+
+/// Implements `Rosy` for a struct or enum.
+///
+/// See the documentation of the `Rosy` trait for details.
+#[proc_macro_derive(Rosy, attributes(rosy))]
+pub fn derive_rosy(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    derive::rosy(input)
+        .unwrap_or_else(|error| error.to_compile_error())
+        .into()
+}
+
+/// Exposes the public functions of an `impl` block as methods of a Ruby class.
+///
+/// See the documentation of `rosy::class` for details.
+#[proc_macro_attribute]
+pub fn class(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let name = parse_macro_input!(attr as LitStr);
+    let item = parse_macro_input!(item as ItemImpl);
+    class::class(name, item)
+        .unwrap_or_else(|error| error.to_compile_error())
+        .into()
+}
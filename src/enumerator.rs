@@ -0,0 +1,492 @@
+//! Ruby enumerators.
+
+use std::{
+    fmt,
+    iter::FusedIterator,
+    os::raw::{c_char, c_int},
+};
+use crate::{
+    exception::StopIteration,
+    gc::Mark,
+    object::NonNullObject,
+    prelude::*,
+    ruby,
+};
+
+/// An instance of Ruby's `Enumerator` class.
+///
+/// # Examples
+///
+/// Items can be retrieved via external enumeration:
+///
+/// ```
+/// # rosy::vm::init().unwrap();
+/// use rosy::prelude::*;
+///
+/// let array: Array = (1..=2).map(Integer::from).collect();
+/// let enumerator = Enumerator::new(array, "each", &[] as &[AnyObject]);
+///
+/// unsafe {
+///     assert_eq!(enumerator.peek().unwrap(), Some(Integer::from(1).into()));
+///     assert_eq!(enumerator.next().unwrap(), Some(Integer::from(1).into()));
+///     assert_eq!(enumerator.next().unwrap(), Some(Integer::from(2).into()));
+///     assert_eq!(enumerator.next().unwrap(), None);
+///
+///     enumerator.rewind();
+///     assert_eq!(enumerator.next().unwrap(), Some(Integer::from(1).into()));
+/// }
+/// ```
+#[derive(Clone, Copy, Debug)]
+#[repr(transparent)]
+pub struct Enumerator(NonNullObject);
+
+impl AsRef<AnyObject> for Enumerator {
+    #[inline]
+    fn as_ref(&self) -> &AnyObject { self.0.as_ref() }
+}
+
+impl From<Enumerator> for AnyObject {
+    #[inline]
+    fn from(obj: Enumerator) -> Self { obj.0.into() }
+}
+
+impl PartialEq<AnyObject> for Enumerator {
+    #[inline]
+    fn eq(&self, obj: &AnyObject) -> bool {
+        self.as_any_object() == obj
+    }
+}
+
+unsafe impl Object for Enumerator {
+    #[inline]
+    fn unique_id() -> Option<u128> {
+        Some((!0) - 3)
+    }
+
+    #[inline]
+    fn cast<A: Object>(obj: A) -> Option<Self> {
+        if obj.class().inherits(Class::enumerator()) {
+            unsafe { Some(Self::cast_unchecked(obj)) }
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for Enumerator {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_any_object().fmt(f)
+    }
+}
+
+impl Enumerator {
+    /// Creates a new instance over the items yielded by calling `method` on
+    /// `object` with `args`.
+    ///
+    /// This is equivalent to `object.enum_for(method, *args)` in Ruby, except
+    /// that `enum_for` is not looked up on `object`. The method itself isn't
+    /// called until `self` is iterated over.
+    #[inline]
+    pub fn new(
+        object: impl Object,
+        method: impl Into<SymbolId>,
+        args: &[impl Object],
+    ) -> Self {
+        let method = Symbol::from(method.into());
+        unsafe { Self::from_raw(ruby::rb_enumeratorize(
+            object.raw(),
+            method.raw(),
+            args.len() as c_int,
+            args.as_ptr() as *const ruby::VALUE,
+        )) }
+    }
+
+    /// Returns the next item, or `None` if `self` is at its end.
+    ///
+    /// # Safety
+    ///
+    /// Code executed from the enumerated method may void the type safety of
+    /// objects accessible from Rust. For example, if one calls `push` on an
+    /// `Array<A>` with an object of type `B`, then the inserted object will be
+    /// treated as being of type `A`.
+    #[inline]
+    pub unsafe fn next(self) -> Result<Option<AnyObject>> {
+        Self::_end_to_none(self.call_protected("next"))
+    }
+
+    /// Returns the next item without advancing, or `None` if `self` is at its
+    /// end.
+    ///
+    /// # Safety
+    ///
+    /// Code executed from the enumerated method may void the type safety of
+    /// objects accessible from Rust. For example, if one calls `push` on an
+    /// `Array<A>` with an object of type `B`, then the inserted object will be
+    /// treated as being of type `A`.
+    #[inline]
+    pub unsafe fn peek(self) -> Result<Option<AnyObject>> {
+        Self::_end_to_none(self.call_protected("peek"))
+    }
+
+    /// Moves `self` back to the start of its items.
+    ///
+    /// # Safety
+    ///
+    /// If the enumerated object responds to `rewind`, it is called and may void
+    /// the type safety of objects accessible from Rust.
+    #[inline]
+    pub unsafe fn rewind(self) {
+        self.call("rewind");
+    }
+
+    /// Returns an iterator over the remaining items of `self`.
+    ///
+    /// # Safety
+    ///
+    /// The returned iterator raises any exception other than `StopIteration`
+    /// that occurs during enumeration. See [`next`](#method.next) for more
+    /// info.
+    #[inline]
+    pub unsafe fn iter(self) -> Each {
+        Each { enumerator: self }
+    }
+
+    /// Returns an iterator over the remaining items of `self`, which yields
+    /// any exception other than `StopIteration` that occurs during enumeration
+    /// before ending.
+    ///
+    /// # Safety
+    ///
+    /// See [`next`](#method.next).
+    #[inline]
+    pub unsafe fn iter_protected(self) -> EachProtected {
+        EachProtected { enumerator: Some(self) }
+    }
+
+    // Turns the `StopIteration` exception raised at the end into `None`
+    #[inline]
+    fn _end_to_none(result: Result<AnyObject>) -> Result<Option<AnyObject>> {
+        match result {
+            Ok(item) => Ok(Some(item)),
+            Err(error) if error.is_stop_iteration() => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+}
+
+/// An iterator over the items of an [`Enumerator`](struct.Enumerator.html).
+///
+/// This is created by [`Object::each`](../object/trait.Object.html#method.each)
+/// or [`Enumerator::iter`](struct.Enumerator.html#method.iter).
+#[derive(Clone, Debug)]
+pub struct Each {
+    enumerator: Enumerator,
+}
+
+impl Iterator for Each {
+    type Item = AnyObject;
+
+    #[inline]
+    fn next(&mut self) -> Option<AnyObject> {
+        unsafe {
+            match self.enumerator.next() {
+                Ok(item) => item,
+                Err(error) => error.raise(),
+            }
+        }
+    }
+}
+
+/// An iterator over the items of an [`Enumerator`](struct.Enumerator.html)
+/// that yields exceptions raised during enumeration.
+///
+/// This is created by
+/// [`Object::each_protected`](../object/trait.Object.html#method.each_protected)
+/// or [`Enumerator::iter_protected`](struct.Enumerator.html#method.iter_protected).
+#[derive(Clone, Debug)]
+pub struct EachProtected {
+    // Set to `None` at the end or after an exception
+    enumerator: Option<Enumerator>,
+}
+
+impl Iterator for EachProtected {
+    type Item = Result<AnyObject>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Result<AnyObject>> {
+        let result = unsafe { self.enumerator?.next() };
+        match result {
+            Ok(Some(item)) => Some(Ok(item)),
+            Ok(None) => {
+                self.enumerator = None;
+                None
+            },
+            Err(error) => {
+                self.enumerator = None;
+                Some(Err(error))
+            },
+        }
+    }
+}
+
+impl FusedIterator for EachProtected {}
+
+/// A Rust iterator that can be enumerated from Ruby.
+///
+/// Instances are of the `RustIterator` class, which includes `Enumerable`.
+/// Items can be enumerated internally via `each` (and thus any `Enumerable`
+/// method) or externally via `next` and `peek`. Items are produced lazily as
+/// they're requested and so the underlying iterator can be consumed only once.
+///
+/// # Examples
+///
+/// ```
+/// # rosy::vm::init().unwrap();
+/// # rosy::protected(|| {
+/// use rosy::{enumerator::RustIterator, prelude::*};
+///
+/// // Safe because the state of the iterator holds no objects
+/// let squares = unsafe {
+///     RustIterator::new_unmarked((1..=4).map(|i| Integer::from(i * i)))
+/// };
+///
+/// unsafe {
+///     assert_eq!(squares.call("peek"), Integer::from(1));
+///     assert_eq!(squares.call("next"), Integer::from(1));
+///     assert_eq!(squares.call("sum"), Integer::from(29));
+/// }
+/// # }).unwrap();
+/// ```
+///
+/// Once exhausted, `next` raises a `StopIteration` exception:
+///
+/// ```
+/// # rosy::vm::init().unwrap();
+/// use rosy::{enumerator::RustIterator, prelude::*};
+///
+/// let iter = RustIterator::new(vec![Integer::from(1)]);
+///
+/// unsafe {
+///     assert_eq!(iter.call_protected("next").unwrap(), Integer::from(1));
+///
+///     let error = iter.call_protected("next").unwrap_err();
+///     assert!(error.is_stop_iteration());
+/// }
+/// ```
+///
+/// Stopping `each` early, such as via `first`, leaves the remaining items to be
+/// enumerated later:
+///
+/// ```
+/// # rosy::vm::init().unwrap();
+/// # rosy::protected(|| {
+/// use rosy::{enumerator::RustIterator, prelude::*};
+///
+/// let ints: Vec<Integer> = (1..=4).map(Integer::from).collect();
+/// let expected = [Integer::from(1), Integer::from(2)];
+/// let iter = RustIterator::new(ints);
+///
+/// unsafe {
+///     let first = iter.call_with("first", &[Integer::from(2)]);
+///     assert_eq!(first, Array::from_slice(&expected));
+///     assert_eq!(iter.call("next"), Integer::from(3));
+/// }
+/// # }).unwrap();
+/// ```
+pub struct RustIterator {
+    iter: Box<dyn RawIter>,
+    peeked: Option<Option<AnyObject>>,
+}
+
+unsafe impl Rosy for RustIterator {
+    const ID: *const c_char = b"rust_iterator\0".as_ptr() as _;
+
+    fn class() -> Class {
+        // A static function pointer that will get swapped out the first time
+        // it's called, after defining the class and its methods
+        static mut GET_RUST_ITERATOR: fn() -> Class = || unsafe {
+            static mut RUST_ITERATOR: AnyObject = unsafe {
+                AnyObject::from_raw(0)
+            };
+
+            let class = Class::get_or_def("RustIterator")
+                .expect("Failed to create 'RustIterator'");
+
+            RUST_ITERATOR = class.into();
+            GET_RUST_ITERATOR = || Class::cast_unchecked(RUST_ITERATOR);
+
+            crate::gc::register_mark(class);
+            RustIterator::_def_methods(class);
+            class.freeze();
+
+            class
+        };
+
+        unsafe { GET_RUST_ITERATOR() }
+    }
+
+    #[inline]
+    fn mark(&self) {
+        self.iter.mark();
+        self.peeked.mark();
+    }
+}
+
+impl RustIterator {
+    /// Creates a new `RustIterator` instance that lazily yields the items of
+    /// `iter` to Ruby, marking the state of the iterator via its
+    /// [`Mark`](../gc/trait.Mark.html) implementation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # rosy::vm::init().unwrap();
+    /// # rosy::protected(|| {
+    /// use rosy::{enumerator::RustIterator, prelude::*};
+    ///
+    /// let strings = vec![String::from("hello"), String::from("world")];
+    /// let iter = RustIterator::new(strings);
+    ///
+    /// // The strings are kept alive by `iter`
+    /// rosy::gc::start();
+    ///
+    /// let array = unsafe { iter.call("join") };
+    /// assert_eq!(array, String::from("helloworld"));
+    /// # }).unwrap();
+    /// ```
+    #[inline]
+    pub fn new<I>(iter: I) -> RosyObject<Self>
+    where
+        I: IntoIterator,
+        I::IntoIter: Mark + 'static,
+        I::Item: Into<AnyObject>,
+    {
+        Self::_new(Box::new(Marked(iter.into_iter())))
+    }
+
+    /// Creates a new `RustIterator` instance that lazily yields the items of
+    /// `iter` to Ruby, without marking the state of the iterator.
+    ///
+    /// This allows for iterators that don't implement
+    /// [`Mark`](../gc/trait.Mark.html), such as ones created via `map`.
+    ///
+    /// # Safety
+    ///
+    /// Ruby objects held by the state of `iter` are not marked by the garbage
+    /// collector. The caller must ensure that any such objects are kept alive
+    /// by other means for as long as the instance may be used. Items that are
+    /// already yielded or peeked don't need to be kept alive.
+    #[inline]
+    pub unsafe fn new_unmarked<I>(iter: I) -> RosyObject<Self>
+    where
+        I: IntoIterator,
+        I::IntoIter: 'static,
+        I::Item: Into<AnyObject>,
+    {
+        Self::_new(Box::new(Unmarked(iter.into_iter())))
+    }
+
+    // monomorphization
+    fn _new(iter: Box<dyn RawIter>) -> RosyObject<Self> {
+        RosyObject::from(RustIterator { iter, peeked: None })
+    }
+
+    fn _next(&mut self) -> Option<AnyObject> {
+        match self.peeked.take() {
+            Some(peeked) => peeked,
+            None => self.iter.next(),
+        }
+    }
+
+    fn _peek(&mut self) -> Option<AnyObject> {
+        let iter = &mut self.iter;
+        *self.peeked.get_or_insert_with(|| iter.next())
+    }
+
+    fn _def_methods(class: Class) {
+        class.include(Module::enumerable());
+        unsafe { ruby::rb_undef_alloc_func(class.raw()) };
+
+        let each: extern "C" fn(_) -> _ = each;
+        let next: extern "C" fn(_) -> _ = next;
+        let peek: extern "C" fn(_) -> _ = peek;
+
+        class.def_method("each", each).expect("Failed to define 'each'");
+        class.def_method("next", next).expect("Failed to define 'next'");
+        class.def_method("peek", peek).expect("Failed to define 'peek'");
+    }
+}
+
+extern "C" fn each(this: AnyObject) -> AnyObject {
+    crate::mixin::_call_method(|| {
+        let this = RosyObject::<RustIterator>::_cast_instance(this)?;
+        if !AnyObject::block_given() {
+            let each = Symbol::from("each");
+            return unsafe { Ok(this.call_with("enum_for", &[each])) };
+        }
+        loop {
+            // The borrow is released before yielding so that the block may
+            // call `next` or `peek`, and so that `break` doesn't skip its drop
+            let item = match this.borrow_mut()?._next() {
+                Some(item) => item,
+                None => return Ok(this.into()),
+            };
+            unsafe { AnyObject::yield_with_unchecked(&[item]) };
+        }
+    })
+}
+
+extern "C" fn next(this: AnyObject) -> AnyObject {
+    crate::mixin::_call_method(|| {
+        let this = RosyObject::<RustIterator>::_cast_instance(this)?;
+        let item = this.borrow_mut()?._next();
+        item.ok_or_else(_stop_iteration)
+    })
+}
+
+extern "C" fn peek(this: AnyObject) -> AnyObject {
+    crate::mixin::_call_method(|| {
+        let this = RosyObject::<RustIterator>::_cast_instance(this)?;
+        let item = this.borrow_mut()?._peek();
+        item.ok_or_else(_stop_iteration)
+    })
+}
+
+#[cold]
+fn _stop_iteration() -> AnyException {
+    StopIteration::new("iteration reached an end").into_any_exception()
+}
+
+// The type-erased iterator behind a `RustIterator`
+trait RawIter {
+    fn next(&mut self) -> Option<AnyObject>;
+
+    fn mark(&self);
+}
+
+struct Unmarked<I>(I);
+
+impl<I: Iterator> RawIter for Unmarked<I> where I::Item: Into<AnyObject> {
+    #[inline]
+    fn next(&mut self) -> Option<AnyObject> {
+        self.0.next().map(Into::into)
+    }
+
+    #[inline]
+    fn mark(&self) {}
+}
+
+struct Marked<I>(I);
+
+impl<I: Iterator + Mark> RawIter for Marked<I> where I::Item: Into<AnyObject> {
+    #[inline]
+    fn next(&mut self) -> Option<AnyObject> {
+        self.0.next().map(Into::into)
+    }
+
+    #[inline]
+    fn mark(&self) {
+        self.0.mark();
+    }
+}
@@ -1,12 +1,12 @@
 //! Ruby exceptions.
 
 use std::{
-    convert::Infallible,
+    convert::{Infallible, TryFrom},
     error::Error,
     fmt,
 };
 use crate::{
-    object::NonNullObject,
+    object::{CastError, NonNullObject},
     prelude::*,
     ruby,
 };
@@ -155,6 +155,7 @@ impl AnyException {
     pub(crate) unsafe fn _take_current() -> AnyException {
         let exc = ruby::rb_errinfo();
         ruby::rb_set_errinfo(crate::util::NIL_VALUE);
+        crate::protected::_resume_panic(exc);
         AnyException::from_raw(exc)
     }
 
@@ -262,6 +263,24 @@ macro_rules! typed_exceptions {
                 }
             }
 
+            impl TryFrom<AnyObject> for $name {
+                type Error = CastError;
+
+                #[inline]
+                fn try_from(obj: AnyObject) -> std::result::Result<Self, CastError> {
+                    Self::cast(obj).ok_or_else(|| CastError::_new::<Self>(obj))
+                }
+            }
+
+            impl crate::gc::Mark for $name {
+                #[inline]
+                fn mark(&self) {
+                    crate::gc::mark(*self);
+                }
+            }
+
+            impl crate::gc::HeapSize for $name {}
+
             impl fmt::Debug for $name {
                 #[inline]
                 fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
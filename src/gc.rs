@@ -1,8 +1,19 @@
 //! Ruby's garbage collector.
 
+use std::{
+    cell::{Cell, RefCell},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
+    marker::PhantomData,
+    mem,
+    rc::Rc,
+    sync::Arc,
+};
 use crate::{
+    num::{AnyNumeric, Complex, Rational},
     prelude::*,
     ruby::{self, VALUE},
+    string::Encoding,
+    vm::InstrSeq,
 };
 
 /// Starts the garbage collector.
@@ -291,3 +302,390 @@ impl GcInfoKey for String {
         Symbol::from(self).latest_gc_info_unchecked()
     }
 }
+
+/// A type that may hold references to Ruby objects, which must be marked
+/// during garbage collection.
+///
+/// This is used by `#[derive(Rosy)]` to implement
+/// [`Rosy::mark`](../trait.Rosy.html#tymethod.mark) by marking each field. It
+/// is implemented for all of Rosy's object types as well as for standard
+/// library types that may contain them.
+///
+/// Types that implement [`Object`](../object/trait.Object.html) outside of
+/// this library should implement this via [`mark`](fn.mark.html).
+pub trait Mark {
+    /// Marks all Ruby objects referenced by `self`.
+    fn mark(&self);
+}
+
+/// A type that may own memory outside of its own size.
+///
+/// This is used by `#[derive(Rosy)]` to implement
+/// [`Rosy::size`](../trait.Rosy.html#method.size) by summing the heap size of
+/// each field. The default of 0 is correct for types that own no memory.
+///
+/// Ruby objects are owned by Ruby and thus count as 0.
+pub trait HeapSize {
+    /// Returns the estimated number of bytes owned by `self`, excluding
+    /// `mem::size_of_val(self)`.
+    #[inline]
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+macro_rules! impl_object {
+    ($($t:ty $(: $($g:ident),+)?;)+) => { $(
+        impl$(<$($g: Object),+>)? Mark for $t {
+            #[inline]
+            fn mark(&self) {
+                mark(*self);
+            }
+        }
+
+        impl$(<$($g: Object),+>)? HeapSize for $t {}
+    )+ };
+}
+
+impl_object! {
+    AnyObject;
+    AnyException;
+    AnyNumeric;
+    Array<O>: O;
+    Class<O>: O;
+    Encoding;
+    Enumerator;
+    Complex;
+    Float;
+    Hash<K, V>: K, V;
+    InstrSeq;
+    Integer;
+    Rational;
+    Module;
+    Proc;
+    Range<S, E>: S, E;
+    String;
+    Symbol;
+}
+
+impl<R: Rosy> Mark for RosyObject<R> {
+    #[inline]
+    fn mark(&self) {
+        mark(*self);
+    }
+}
+
+impl<R: Rosy> HeapSize for RosyObject<R> {}
+
+macro_rules! impl_leaf {
+    ($($t:ty)+) => { $(
+        impl Mark for $t {
+            #[inline]
+            fn mark(&self) {}
+        }
+
+        impl HeapSize for $t {}
+    )+ };
+}
+
+impl_leaf! {
+    () bool char f32 f64 str SymbolId
+    usize u128 u64 u32 u16 u8
+    isize i128 i64 i32 i16 i8
+}
+
+impl<T: ?Sized> Mark for PhantomData<T> {
+    #[inline]
+    fn mark(&self) {}
+}
+
+impl<T: ?Sized> HeapSize for PhantomData<T> {}
+
+impl<T: Copy + Mark> Mark for Cell<T> {
+    #[inline]
+    fn mark(&self) {
+        self.get().mark();
+    }
+}
+
+impl<T: Copy + HeapSize> HeapSize for Cell<T> {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.get().heap_size()
+    }
+}
+
+impl Mark for std::string::String {
+    #[inline]
+    fn mark(&self) {}
+}
+
+impl HeapSize for std::string::String {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.capacity()
+    }
+}
+
+impl<T: Mark + ?Sized> Mark for &T {
+    #[inline]
+    fn mark(&self) {
+        (**self).mark();
+    }
+}
+
+/// The referenced value is not owned and thus counts as 0.
+impl<T: ?Sized> HeapSize for &T {}
+
+impl<T: Mark + ?Sized> Mark for &mut T {
+    #[inline]
+    fn mark(&self) {
+        (**self).mark();
+    }
+}
+
+/// The referenced value is not owned and thus counts as 0.
+impl<T: ?Sized> HeapSize for &mut T {}
+
+impl<T: Mark + ?Sized> Mark for Box<T> {
+    #[inline]
+    fn mark(&self) {
+        (**self).mark();
+    }
+}
+
+impl<T: HeapSize + ?Sized> HeapSize for Box<T> {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        mem::size_of_val(&**self) + (**self).heap_size()
+    }
+}
+
+impl<T: Mark + ?Sized> Mark for Rc<T> {
+    #[inline]
+    fn mark(&self) {
+        (**self).mark();
+    }
+}
+
+/// The shared value is counted in full by each `Rc` that points to it.
+impl<T: HeapSize + ?Sized> HeapSize for Rc<T> {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        mem::size_of_val(&**self) + (**self).heap_size()
+    }
+}
+
+impl<T: Mark + ?Sized> Mark for Arc<T> {
+    #[inline]
+    fn mark(&self) {
+        (**self).mark();
+    }
+}
+
+/// The shared value is counted in full by each `Arc` that points to it.
+impl<T: HeapSize + ?Sized> HeapSize for Arc<T> {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        mem::size_of_val(&**self) + (**self).heap_size()
+    }
+}
+
+/// Marks even if `self` is mutably borrowed, since the borrower may be what
+/// triggered garbage collection.
+impl<T: Mark + ?Sized> Mark for RefCell<T> {
+    #[inline]
+    fn mark(&self) {
+        unsafe { (*self.as_ptr()).mark() };
+    }
+}
+
+impl<T: HeapSize + ?Sized> HeapSize for RefCell<T> {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.try_borrow().map(|inner| inner.heap_size()).unwrap_or(0)
+    }
+}
+
+impl<T: Mark> Mark for Option<T> {
+    #[inline]
+    fn mark(&self) {
+        if let Some(value) = self {
+            value.mark();
+        }
+    }
+}
+
+impl<T: HeapSize> HeapSize for Option<T> {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.as_ref().map(HeapSize::heap_size).unwrap_or(0)
+    }
+}
+
+impl<T: Mark, E: Mark> Mark for std::result::Result<T, E> {
+    #[inline]
+    fn mark(&self) {
+        match self {
+            Ok(value) => value.mark(),
+            Err(error) => error.mark(),
+        }
+    }
+}
+
+impl<T: HeapSize, E: HeapSize> HeapSize for std::result::Result<T, E> {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        match self {
+            Ok(value) => value.heap_size(),
+            Err(error) => error.heap_size(),
+        }
+    }
+}
+
+impl<T: Mark> Mark for [T] {
+    #[inline]
+    fn mark(&self) {
+        self.iter().for_each(Mark::mark);
+    }
+}
+
+impl<T: HeapSize> HeapSize for [T] {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.iter().map(HeapSize::heap_size).sum()
+    }
+}
+
+macro_rules! impl_array {
+    ($($n:expr)+) => { $(
+        impl<T: Mark> Mark for [T; $n] {
+            #[inline]
+            fn mark(&self) {
+                self[..].mark();
+            }
+        }
+
+        impl<T: HeapSize> HeapSize for [T; $n] {
+            #[inline]
+            fn heap_size(&self) -> usize {
+                self[..].heap_size()
+            }
+        }
+    )+ };
+}
+
+impl_array! {
+    0  1  2  3  4  5  6  7  8  9  10 11 12 13 14 15 16
+    17 18 19 20 21 22 23 24 25 26 27 28 29 30 31 32
+}
+
+macro_rules! impl_collection {
+    ($($c:ident<$($t:ident),+> => $elem:expr;)+) => { $(
+        impl<$($t: Mark),+> Mark for $c<$($t),+> {
+            #[inline]
+            #[allow(non_snake_case)]
+            fn mark(&self) {
+                for ($($t,)+) in self.iter().map($elem) {
+                    $($t.mark();)+
+                }
+            }
+        }
+
+        /// Includes the space reserved for elements.
+        impl<$($t: HeapSize),+> HeapSize for $c<$($t),+> {
+            #[inline]
+            #[allow(non_snake_case)]
+            fn heap_size(&self) -> usize {
+                let reserved = _capacity(self) * mem::size_of::<($($t,)+)>();
+                self.iter().map($elem).fold(reserved, |size, ($($t,)+)| {
+                    size $(+ $t.heap_size())+
+                })
+            }
+        }
+    )+ };
+}
+
+impl_collection! {
+    Vec<T>          => |t| (t,);
+    VecDeque<T>     => |t| (t,);
+    HashSet<T>      => |t| (t,);
+    BTreeSet<T>     => |t| (t,);
+    HashMap<K, V>   => |(k, v)| (k, v);
+    BTreeMap<K, V>  => |(k, v)| (k, v);
+}
+
+/// Marks the elements that have yet to be iterated over.
+impl<T: Mark> Mark for std::vec::IntoIter<T> {
+    #[inline]
+    fn mark(&self) {
+        self.as_slice().mark();
+    }
+}
+
+// The number of elements that a collection has reserved space for
+trait _Capacity {
+    fn _capacity(&self) -> usize;
+}
+
+#[inline]
+fn _capacity(c: &impl _Capacity) -> usize {
+    c._capacity()
+}
+
+macro_rules! impl_capacity {
+    ($($c:ident<$($t:ident),+> => $f:ident;)+) => { $(
+        impl<$($t),+> _Capacity for $c<$($t),+> {
+            #[inline]
+            fn _capacity(&self) -> usize {
+                self.$f()
+            }
+        }
+    )+ };
+}
+
+impl_capacity! {
+    Vec<T>          => capacity;
+    VecDeque<T>     => capacity;
+    HashSet<T>      => capacity;
+    BTreeSet<T>     => len;
+    HashMap<K, V>   => capacity;
+    BTreeMap<K, V>  => len;
+}
+
+macro_rules! impl_tuple {
+    ($(($($t:ident)+))+) => { $(
+        impl<$($t: Mark),+> Mark for ($($t,)+) {
+            #[inline]
+            #[allow(non_snake_case)]
+            fn mark(&self) {
+                let ($($t,)+) = self;
+                $($t.mark();)+
+            }
+        }
+
+        impl<$($t: HeapSize),+> HeapSize for ($($t,)+) {
+            #[inline]
+            #[allow(non_snake_case)]
+            fn heap_size(&self) -> usize {
+                let ($($t,)+) = self;
+                0 $(+ $t.heap_size())+
+            }
+        }
+    )+ };
+}
+
+impl_tuple! {
+    (A)
+    (A B)
+    (A B C)
+    (A B C D)
+    (A B C D E)
+    (A B C D E F)
+    (A B C D E F G)
+    (A B C D E F G H)
+    (A B C D E F G H I)
+    (A B C D E F G H I J)
+    (A B C D E F G H I J K)
+    (A B C D E F G H I J K L)
+}
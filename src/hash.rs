@@ -1,9 +1,13 @@
 //! Ruby hash tables.
 
 use std::{
+    any::Any,
+    convert::Infallible,
     fmt,
     iter::FromIterator,
     marker::PhantomData,
+    os::raw::c_int,
+    panic::{self, AssertUnwindSafe},
 };
 use crate::{
     object::{NonNullObject, Ty},
@@ -257,6 +261,98 @@ impl<K: Object, V: Object> Hash<K, V> {
         self.len() == 0
     }
 
+    /// Calls `f` on each key-value pair in `self`, in insertion order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # rosy::vm::init().unwrap();
+    /// use rosy::prelude::*;
+    ///
+    /// let hash: Hash<String, Integer> = vec![("a", 1), ("b", 2)].into_iter()
+    ///     .map(|(k, v)| (String::from(k), Integer::from(v)))
+    ///     .collect();
+    ///
+    /// let mut sum = 0;
+    /// hash.for_each(|_, val| sum += val.to_value::<i32>().unwrap());
+    ///
+    /// assert_eq!(sum, 3);
+    /// ```
+    #[inline]
+    pub fn for_each<F>(self, mut f: F)
+        where F: FnMut(K, V)
+    {
+        let result = self.try_for_each(|key, val| -> Result<(), Infallible> {
+            f(key, val);
+            Ok(())
+        });
+        match result {
+            Ok(()) => {},
+            Err(never) => match never {},
+        }
+    }
+
+    /// Calls `f` on each key-value pair in `self`, in insertion order,
+    /// stopping at the first error returned.
+    ///
+    /// If `f` panics, iteration stops and the panic is resumed once control
+    /// returns to Rust.
+    pub fn try_for_each<F, E>(self, f: F) -> Result<(), E>
+        where F: FnMut(K, V) -> Result<(), E>
+    {
+        const ST_CONTINUE: c_int = 0;
+        const ST_STOP: c_int = 1;
+
+        struct Context<F, E> {
+            f: F,
+            result: Result<(), E>,
+            panic: Option<Box<dyn Any + Send>>,
+        }
+
+        unsafe extern "C" fn callback<K, V, F, E>(
+            key: ruby::VALUE,
+            val: ruby::VALUE,
+            ctx: ruby::VALUE,
+        ) -> c_int
+        where
+            K: Object,
+            V: Object,
+            F: FnMut(K, V) -> Result<(), E>,
+        {
+            let ctx = &mut *(ctx as *mut Context<F, E>);
+            let f = &mut ctx.f;
+            let key = K::from_raw(key);
+            let val = V::from_raw(val);
+
+            // Unwinding across the C stack is undefined behavior, so the panic
+            // is stored and resumed after `rb_hash_foreach` returns
+            match panic::catch_unwind(AssertUnwindSafe(|| f(key, val))) {
+                Ok(Ok(())) => ST_CONTINUE,
+                Ok(Err(error)) => {
+                    ctx.result = Err(error);
+                    ST_STOP
+                },
+                Err(panic) => {
+                    ctx.panic = Some(panic);
+                    ST_STOP
+                },
+            }
+        }
+
+        let mut ctx = Context { f, result: Ok(()), panic: None };
+        unsafe {
+            ruby::rb_hash_foreach(
+                self.raw(),
+                Some(callback::<K, V, F, E>),
+                &mut ctx as *mut Context<F, E> as ruby::VALUE,
+            );
+        }
+        if let Some(panic) = ctx.panic {
+            panic::resume_unwind(panic);
+        }
+        ctx.result
+    }
+
     /// Removes the value associated with `key` from `self` and returns it.
     ///
     /// # Safety
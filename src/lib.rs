@@ -16,6 +16,16 @@
 //! following features can currently be enabled:
 //!
 //! - `ruby_2_6`
+//! - `ruby_2_7`
+//!
+//! Procedural macros like `#[derive(Rosy)]` are available via the `macros`
+//! feature.
+//!
+//! Converting between Rust data and Ruby objects via [Serde] is available via
+//! the `serde` feature.
+//!
+//! Conversions between `Integer` and [`num-bigint`] types are available via the
+//! `num-bigint` feature.
 //!
 //! For example:
 //!
@@ -83,6 +93,8 @@
 //! [`Cargo.toml`]: https://doc.rust-lang.org/cargo/reference/manifest.html
 //! [crate]: https://crates.io/crates/rosy
 //! [Ruby]: https://www.ruby-lang.org
+//! [Serde]: https://serde.rs
+//! [`num-bigint`]: https://crates.io/crates/num-bigint
 //! [`vm::init`]: vm/fn.init.html
 //! [`vm::destroy`]: vm/fn.destroy.html
 //! [`unsafe`]: https://doc.rust-lang.org/book/ch19-01-unsafe-rust.html
@@ -106,17 +118,23 @@ mod rosy;
 mod protected;
 mod util;
 pub mod array;
+pub mod enumerator;
 pub mod exception;
 pub mod gc;
 pub mod hash;
+pub mod marshal;
 pub mod meta;
 pub mod mixin;
 pub mod num;
 pub mod object;
 pub mod prelude;
+pub mod proc;
 pub mod range;
+#[cfg(feature = "serde")]
+pub mod serde;
 pub mod string;
 pub mod symbol;
+pub mod value;
 pub mod vm;
 
 #[doc(inline)]
@@ -125,17 +143,155 @@ pub use protected::*;
 #[doc(inline)] // prelude
 pub use self::{
     array::Array,
+    enumerator::Enumerator,
     exception::{AnyException, Exception},
     hash::Hash,
     mixin::{Mixin, Class, Module},
     num::{Float, Integer},
-    object::{AnyObject, Object, RosyObject},
+    object::{AnyObject, FromRuby, FromRubyOwned, IntoRuby, Object, RosyObject},
+    proc::Proc,
     range::Range,
-    rosy::Rosy,
+    rosy::{Initialize, Rosy},
     string::String,
     symbol::{Symbol, SymbolId},
+    value::Value,
 };
 
+#[cfg(feature = "macros")]
+#[doc(inline)]
+pub use rosy_macros::Rosy;
+
+/// Exposes the public functions of an `impl` block as methods of a Ruby class.
+///
+/// **Requires feature:** `macros`
+///
+/// This generates a `define(parent: impl Mixin) -> Result<Class>` function on
+/// the type, which defines the class under `parent` and registers each `pub
+/// fn` in the block as a method:
+///
+/// - `fn new(...) -> Self` becomes `initialize`, which sets the data of an
+///   instance allocated by `Class.new`. It may also return `Result<Self, E>`
+///   where `E` is an [`Exception`](exception/trait.Exception.html). Without
+///   `new`, instances can only be created from Rust.
+///
+/// - `&self` and `&mut self` methods become instance methods that access the
+///   wrapped data. Calling one on an object that isn't an instance of the
+///   class raises a `TypeError`.
+///
+/// - Functions without a receiver become singleton methods on the class.
+///
+/// Arguments are converted via [`FromRuby`](trait.FromRuby.html) and return
+/// values via [`IntoRuby`](trait.IntoRuby.html), just like with
+/// [`def_method!`](macro.def_method.html). A method can be given a different
+/// Ruby name via `#[rosy(name = "...")]` or be left out via `#[rosy(skip)]`.
+///
+/// The type must implement [`Rosy`](trait.Rosy.html), such as via
+/// `#[derive(Rosy)]`, and its [`class`](trait.Rosy.html#method.class) should
+/// be the one defined here so that instances created from Rust get the same
+/// methods.
+///
+/// The data is accessed via [`RosyObject::borrow`](struct.RosyObject.html#method.borrow)
+/// and [`RosyObject::borrow_mut`](struct.RosyObject.html#method.borrow_mut).
+/// If a method causes Ruby to call back into a method on the same object and
+/// the borrows conflict, a `RuntimeError` is raised. The borrow ends with the
+/// method, even if it raises or its block is broken out of.
+///
+/// # Examples
+///
+#[cfg_attr(feature = "macros", doc = "```")]
+#[cfg_attr(not(feature = "macros"), doc = "```ignore")]
+/// # rosy::vm::init().unwrap();
+/// # rosy::protected(|| {
+/// use std::ffi::CStr;
+/// use rosy::prelude::*;
+///
+/// #[derive(Rosy)]
+/// #[rosy(class = "Counter")]
+/// struct Counter {
+///     count: i64,
+/// }
+///
+/// #[rosy::class("Counter")]
+/// impl Counter {
+///     pub fn new(count: i64) -> Self {
+///         Counter { count }
+///     }
+///
+///     pub fn count(&self) -> i64 {
+///         self.count
+///     }
+///
+///     pub fn increment(&mut self, by: i64) {
+///         self.count += by;
+///     }
+///
+///     #[rosy(name = "zero?")]
+///     pub fn is_zero(&self) -> bool {
+///         self.count == 0
+///     }
+///
+///     pub fn max() -> i64 {
+///         i64::max_value()
+///     }
+/// }
+///
+/// Counter::define(Class::object()).unwrap();
+///
+/// let script = b"c = Counter.new(1); c.increment(2); [c.count, c.zero?]\0";
+/// let script = CStr::from_bytes_with_nul(script).unwrap();
+///
+/// let output = unsafe { rosy::vm::eval(script) };
+/// let expected = [AnyObject::from(3), AnyObject::from(false)];
+/// assert_eq!(output, &expected[..]);
+///
+/// let counter = RosyObject::from(Counter { count: 0 });
+/// assert!(unsafe { counter.call("zero?") }.is_true());
+/// # }).unwrap();
+/// ```
+///
+/// A method that yields can be broken out of without leaving its object
+/// borrowed:
+///
+#[cfg_attr(feature = "macros", doc = "```")]
+#[cfg_attr(not(feature = "macros"), doc = "```ignore")]
+/// # rosy::vm::init().unwrap();
+/// # rosy::protected(|| {
+/// use std::ffi::CStr;
+/// use rosy::prelude::*;
+///
+/// #[derive(Rosy)]
+/// #[rosy(class = "Stepper")]
+/// struct Stepper {
+///     step: i64,
+/// }
+///
+/// #[rosy::class("Stepper")]
+/// impl Stepper {
+///     pub fn new() -> Self {
+///         Stepper { step: 0 }
+///     }
+///
+///     pub fn step(&mut self) -> AnyObject {
+///         self.step += 1;
+///         unsafe { AnyObject::yield_with_unchecked(&[Integer::from(self.step)]) }
+///     }
+/// }
+///
+/// Stepper::define(Class::object()).unwrap();
+///
+/// let script = b"s = Stepper.new; s.step { break }; s.step { |n| n }\0";
+/// let script = CStr::from_bytes_with_nul(script).unwrap();
+///
+/// let output = unsafe { rosy::vm::eval(script) };
+/// assert_eq!(output, Integer::from(2));
+/// # }).unwrap();
+/// ```
+#[cfg(feature = "macros")]
+pub use rosy_macros::class;
+
+#[doc(hidden)]
+pub use self::rosy::{_class_at_path, _unique_object_id};
+
 /// A simplified form of
 /// [`Result`](https://doc.rust-lang.org/std/result/enum.Result.html) for
 /// when exceptions are caught.
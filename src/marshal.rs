@@ -0,0 +1,206 @@
+//! Serializing Ruby objects via Ruby's `Marshal` format.
+//!
+//! # Examples
+//!
+//! Objects can be written to and read from any I/O type:
+//!
+//! ```
+//! # rosy::vm::init().unwrap();
+//! use rosy::{marshal, prelude::*};
+//!
+//! let array: Array = (1..=3).map(Integer::from).collect();
+//!
+//! let mut bytes = Vec::new();
+//! marshal::dump(array, &mut bytes).unwrap();
+//!
+//! let loaded = unsafe { marshal::load(&bytes[..]).unwrap() };
+//! assert_eq!(loaded, array);
+//! ```
+
+use std::{
+    error::Error,
+    fmt,
+    io::{self, Read, Write},
+};
+use crate::{
+    prelude::*,
+    ruby,
+};
+
+/// Allows for `RosyObject<Self>` instances to be marshalled by Ruby via
+/// `_dump` and `_load` methods.
+///
+/// These methods are defined on the class of `Self` by
+/// [`Class::def_marshal`](../struct.Class.html#method.def_marshal).
+///
+/// # Examples
+///
+/// ```
+/// # rosy::vm::init().unwrap();
+/// # rosy::protected(|| {
+/// use std::os::raw::c_char;
+/// use rosy::{exception::ArgumentError, marshal::{self, Marshal}, prelude::*};
+///
+/// struct Point(u8, u8);
+///
+/// unsafe impl Rosy for Point {
+///     const ID: *const c_char = b"rosy_point\0".as_ptr() as _;
+///
+///     fn class() -> Class {
+///         Class::get_or_def("Point").unwrap()
+///     }
+///
+///     // Allows for casting instances created by `Marshal.load`
+///     fn cast<A: Object>(obj: A) -> Option<RosyObject<Self>> {
+///         unsafe { RosyObject::cast_by_id(obj) }
+///     }
+///
+///     fn mark(&self) {}
+/// }
+///
+/// impl Marshal for Point {
+///     fn dump(&self) -> Result<Vec<u8>> {
+///         Ok(vec![self.0, self.1])
+///     }
+///
+///     fn load(bytes: &[u8]) -> Result<Self> {
+///         if bytes.len() == 2 {
+///             Ok(Point(bytes[0], bytes[1]))
+///         } else {
+///             Err(ArgumentError::new("bad point").into_any_exception())
+///         }
+///     }
+/// }
+///
+/// Class::of::<RosyObject<Point>>().def_marshal().unwrap();
+///
+/// let mut bytes = Vec::new();
+/// marshal::dump(RosyObject::from(Point(1, 2)), &mut bytes).unwrap();
+///
+/// let loaded = unsafe { marshal::load(&bytes[..]).unwrap() };
+/// let point = RosyObject::<Point>::cast(loaded).unwrap();
+/// let point = point.borrow().unwrap();
+/// assert_eq!((point.0, point.1), (1, 2));
+/// # }).unwrap();
+/// ```
+pub trait Marshal: Rosy {
+    /// Serializes `self` into bytes, called by Ruby via `_dump`.
+    fn dump(&self) -> Result<Vec<u8>>;
+
+    /// Deserializes an instance from `bytes` that were created via
+    /// [`dump`](#tymethod.dump), called by Ruby via `_load`.
+    fn load(bytes: &[u8]) -> Result<Self>;
+}
+
+/// Writes `object` to `writer` in Ruby's `Marshal` format.
+///
+/// This is equivalent to `Marshal.dump(object, io)` in Ruby.
+///
+/// # Errors
+///
+/// An exception is returned if `object` can't be marshalled, such as a `Proc`
+/// or a [`RosyObject`](../object/struct.RosyObject.html) whose type doesn't
+/// implement [`Marshal`](trait.Marshal.html).
+#[inline]
+pub fn dump(object: impl Object, writer: impl Write) -> Result<(), MarshalError> {
+    _dump(object.into(), &mut { writer })
+}
+
+// monomorphization
+fn _dump(object: AnyObject, writer: &mut dyn Write) -> Result<(), MarshalError> {
+    let string = unsafe {
+        crate::protected_no_panic(|| String::from_raw(ruby::rb_marshal_dump(
+            object.raw(),
+            crate::util::NIL_VALUE,
+        )))?
+    };
+    writer.write_all(unsafe { string.as_bytes() })?;
+    Ok(())
+}
+
+/// Reads an object from `reader` in Ruby's `Marshal` format.
+///
+/// This is equivalent to `Marshal.load(io)` in Ruby, except that `reader` is
+/// read to its end.
+///
+/// # Safety
+///
+/// Loading may instantiate objects of any class and call their `_load` or
+/// `marshal_load` methods. Because of this, it should _never_ be used with
+/// untrusted data.
+///
+/// Code executed from these methods may void the type safety of objects
+/// accessible from Rust. For example, if one calls `push` on an `Array<A>` with
+/// an object of type `B`, then the inserted object will be treated as being of
+/// type `A`.
+#[inline]
+pub unsafe fn load(reader: impl Read) -> Result<AnyObject, MarshalError> {
+    _load(&mut { reader })
+}
+
+// monomorphization
+unsafe fn _load(reader: &mut dyn Read) -> Result<AnyObject, MarshalError> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+
+    let string = String::from(&bytes[..]);
+    let object = crate::protected_no_panic(|| {
+        AnyObject::from_raw(ruby::rb_marshal_load(string.raw()))
+    })?;
+    Ok(object)
+}
+
+/// An error when dumping or loading via [`dump`](fn.dump.html) or
+/// [`load`](fn.load.html).
+#[derive(Debug)]
+pub enum MarshalError {
+    /// An exception was raised by Ruby.
+    Exception(AnyException),
+    /// Writing to or reading from the I/O type failed.
+    Io(io::Error),
+}
+
+impl From<AnyException> for MarshalError {
+    #[inline]
+    fn from(exception: AnyException) -> Self {
+        MarshalError::Exception(exception)
+    }
+}
+
+impl From<io::Error> for MarshalError {
+    #[inline]
+    fn from(error: io::Error) -> Self {
+        MarshalError::Io(error)
+    }
+}
+
+impl fmt::Display for MarshalError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MarshalError::Exception(exception) => exception.fmt(f),
+            MarshalError::Io(error) => error.fmt(f),
+        }
+    }
+}
+
+impl Error for MarshalError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            MarshalError::Exception(exception) => Some(exception),
+            MarshalError::Io(error) => Some(error),
+        }
+    }
+}
+
+impl MarshalError {
+    /// Returns the exception raised by Ruby, if any.
+    #[inline]
+    pub fn exception(&self) -> Option<AnyException> {
+        match *self {
+            MarshalError::Exception(exception) => Some(exception),
+            MarshalError::Io(_) => None,
+        }
+    }
+}
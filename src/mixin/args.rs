@@ -0,0 +1,407 @@
+use std::{
+    fmt::Write,
+    os::raw::c_int,
+};
+use crate::{
+    exception::ArgumentError,
+    prelude::*,
+};
+
+/// A specification of the arguments accepted by a Rust-defined method, used to
+/// parse them into [`Args`](struct.Args.html).
+///
+/// This follows the same rules as arguments declared in a Ruby method
+/// definition, in the order of:
+///
+/// ```ruby
+/// def method(required, optional = nil, *splat, trailing, key:, opt_key: nil, **extra, &block)
+/// ```
+///
+/// # Examples
+///
+/// Methods with an arity of `-1` receive their arguments as a C array, which
+/// can be parsed by a spec:
+///
+/// ```
+/// # rosy::vm::init().unwrap();
+/// # rosy::protected(|| {
+/// use std::os::raw::c_int;
+/// use rosy::{mixin::{ArgSpec, Args}, prelude::*};
+///
+/// fn greet(args: &Args) -> Result<AnyObject> {
+///     let name: &str = args.required(0)?;
+///     let greeting: Option<&str> = args.optional(0)?;
+///     let shout: Option<bool> = args.keyword("shout")?;
+///
+///     let message = format!("{}, {}", greeting.unwrap_or("Hello"), name);
+///     if shout == Some(true) {
+///         Ok(String::from(message.to_uppercase().as_str()).into())
+///     } else {
+///         Ok(String::from(message.as_str()).into())
+///     }
+/// }
+///
+/// unsafe extern "C" fn greet_raw(
+///     _this: AnyObject,
+///     argc: c_int,
+///     argv: *const AnyObject,
+/// ) -> AnyObject {
+///     let spec = ArgSpec::new()
+///         .required(1)
+///         .optional(1)
+///         .optional_keyword("shout");
+///
+///     match spec.parse_raw(argc, argv).and_then(|args| greet(&args)) {
+///         Ok(output) => output,
+///         Err(error) => error.raise(),
+///     }
+/// }
+///
+/// let greet: unsafe extern fn(_, _, _) -> _ = greet_raw;
+/// Class::object().def_method("greet", greet).unwrap();
+///
+/// let object = AnyObject::nil();
+/// let name = AnyObject::from("Ruby");
+///
+/// let output = unsafe { object.call_with("greet", &[name]) };
+/// assert_eq!(output, String::from("Hello, Ruby"));
+///
+/// let kwargs: Hash = vec![(Symbol::from("shout"), AnyObject::from(true))]
+///     .into_iter()
+///     .collect();
+/// let output = unsafe { object.call_with("greet", &[name, kwargs.into()]) };
+/// assert_eq!(output, String::from("HELLO, RUBY"));
+///
+/// let error = unsafe { object.call_protected("greet") }.unwrap_err();
+/// assert!(error.is_arg_error());
+/// # }).unwrap();
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ArgSpec {
+    required: usize,
+    optional: usize,
+    splat: bool,
+    trailing: usize,
+    keywords: Vec<(SymbolId, bool)>,
+    extra_keywords: bool,
+    block: bool,
+}
+
+/// Arguments parsed by an [`ArgSpec`](struct.ArgSpec.html).
+///
+/// The objects here are kept alive by the method call that they were passed
+/// to and so this should not outlive that call.
+///
+/// Arguments can be converted to Rust types via [`FromRuby`] by methods such
+/// as [`required`](#method.required) and [`keyword`](#method.keyword).
+///
+/// [`FromRuby`]: ../trait.FromRuby.html
+#[derive(Clone, Debug, Default)]
+pub struct Args {
+    /// Leading required arguments.
+    pub required: Vec<AnyObject>,
+    /// Optional arguments, which are `None` if not given.
+    pub optional: Vec<Option<AnyObject>>,
+    /// Arguments collected by the splat, if accepted.
+    pub splat: Vec<AnyObject>,
+    /// Trailing required arguments.
+    pub trailing: Vec<AnyObject>,
+    /// Keyword arguments in the order declared, which are `None` if optional
+    /// and not given.
+    pub keywords: Vec<(SymbolId, Option<AnyObject>)>,
+    /// Keyword arguments not declared, if accepted.
+    pub extra_keywords: Vec<(Symbol, AnyObject)>,
+    /// The block given to the method, if accepted.
+    pub block: Option<Proc>,
+}
+
+// Used in place of arguments that weren't given
+const NIL: AnyObject = AnyObject::nil();
+
+impl Args {
+    /// Converts the leading required argument at `index` into `T`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    #[inline]
+    pub fn required<'a, T: FromRuby<'a>>(&'a self, index: usize) -> Result<T> {
+        T::from_ruby(&self.required[index])
+    }
+
+    /// Converts the optional argument at `index` into `T`, or `nil` if it
+    /// wasn't given.
+    ///
+    /// Taking `Option<T>` results in `None` when the argument wasn't given.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    #[inline]
+    pub fn optional<'a, T: FromRuby<'a>>(&'a self, index: usize) -> Result<T> {
+        T::from_ruby(self.optional[index].as_ref().unwrap_or(&NIL))
+    }
+
+    /// Converts each argument collected by the splat into `T`.
+    #[inline]
+    pub fn splat<'a, T: FromRuby<'a>>(&'a self) -> Result<Vec<T>> {
+        self.splat.iter().map(T::from_ruby).collect()
+    }
+
+    /// Converts the trailing required argument at `index` into `T`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    #[inline]
+    pub fn trailing<'a, T: FromRuby<'a>>(&'a self, index: usize) -> Result<T> {
+        T::from_ruby(&self.trailing[index])
+    }
+
+    /// Converts the keyword argument for `name` into `T`, or `nil` if it
+    /// wasn't given.
+    ///
+    /// Taking `Option<T>` results in `None` when the argument wasn't given.
+    #[inline]
+    pub fn keyword<'a, T: FromRuby<'a>>(
+        &'a self,
+        name: impl Into<SymbolId>,
+    ) -> Result<T> {
+        let name = name.into();
+        let value = self.keywords.iter()
+            .find(|(key, _)| key.raw() == name.raw())
+            .and_then(|(_, val)| val.as_ref());
+        T::from_ruby(value.unwrap_or(&NIL))
+    }
+}
+
+impl ArgSpec {
+    /// Creates a new spec that accepts no arguments.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accepts `n` leading required arguments.
+    #[inline]
+    pub fn required(mut self, n: usize) -> Self {
+        self.required = n;
+        self
+    }
+
+    /// Accepts `n` optional arguments after the leading required arguments.
+    #[inline]
+    pub fn optional(mut self, n: usize) -> Self {
+        self.optional = n;
+        self
+    }
+
+    /// Accepts any number of arguments after the optional arguments.
+    #[inline]
+    pub fn splat(mut self) -> Self {
+        self.splat = true;
+        self
+    }
+
+    /// Accepts `n` required arguments after the splat.
+    #[inline]
+    pub fn trailing(mut self, n: usize) -> Self {
+        self.trailing = n;
+        self
+    }
+
+    /// Accepts a required keyword argument for `name`.
+    #[inline]
+    pub fn required_keyword(mut self, name: impl Into<SymbolId>) -> Self {
+        self.keywords.push((name.into(), true));
+        self
+    }
+
+    /// Accepts an optional keyword argument for `name`.
+    #[inline]
+    pub fn optional_keyword(mut self, name: impl Into<SymbolId>) -> Self {
+        self.keywords.push((name.into(), false));
+        self
+    }
+
+    /// Accepts keyword arguments that aren't declared.
+    #[inline]
+    pub fn extra_keywords(mut self) -> Self {
+        self.extra_keywords = true;
+        self
+    }
+
+    /// Accepts the block given to the method.
+    #[inline]
+    pub fn block(mut self) -> Self {
+        self.block = true;
+        self
+    }
+
+    #[inline]
+    fn takes_keywords(&self) -> bool {
+        self.extra_keywords || !self.keywords.is_empty()
+    }
+
+    /// Parses `args` according to `self`.
+    ///
+    /// # Errors
+    ///
+    /// An `ArgumentError` exception is returned if `args` does not match
+    /// `self`, with the same message Ruby would give.
+    pub fn parse(&self, args: &[AnyObject]) -> Result<Args> {
+        let required = self.required + self.trailing;
+
+        let (positional, kwargs) = match args.split_last() {
+            Some((&last, rest)) if self.takes_keywords() && args.len() > required => {
+                match _keyword_hash(last) {
+                    Some(hash) => (rest, Some(hash)),
+                    None => (args, None),
+                }
+            },
+            _ => (args, None),
+        };
+
+        let given = positional.len();
+        let max = required + self.optional;
+        if given < required || (!self.splat && given > max) {
+            return Err(self._arity_error(given, kwargs));
+        }
+
+        let (leading, rest) = positional.split_at(self.required);
+        let (rest, trailing) = rest.split_at(rest.len() - self.trailing);
+        let optional_given = rest.len().min(self.optional);
+        let (optional, splat) = rest.split_at(optional_given);
+
+        let mut parsed = Args {
+            required: leading.to_vec(),
+            optional: optional.iter().copied().map(Some).collect(),
+            splat: splat.to_vec(),
+            trailing: trailing.to_vec(),
+            ..Args::default()
+        };
+        parsed.optional.resize(self.optional, None);
+
+        self._parse_keywords(kwargs, &mut parsed)?;
+
+        if self.block {
+            parsed.block = Proc::from_block();
+        }
+        Ok(parsed)
+    }
+
+    /// Parses `argc` arguments at `argv` according to `self`.
+    ///
+    /// This is intended for methods defined with an arity of `-1`.
+    ///
+    /// # Safety
+    ///
+    /// `argv` must point to `argc` valid objects, or be null if `argc` is 0.
+    #[inline]
+    pub unsafe fn parse_raw(
+        &self,
+        argc: c_int,
+        argv: *const AnyObject,
+    ) -> Result<Args> {
+        self.parse(crate::proc::_args(argc, argv as _))
+    }
+
+    fn _parse_keywords(
+        &self,
+        kwargs: Option<Hash<Symbol, AnyObject>>,
+        parsed: &mut Args,
+    ) -> Result {
+        let mut given = Vec::new();
+        if let Some(kwargs) = kwargs {
+            kwargs.for_each(|key, val| given.push((key, val)));
+        }
+
+        let mut missing = Vec::new();
+        for &(name, required) in &self.keywords {
+            let index = given.iter().position(|(key, _)| key.id().raw() == name.raw());
+            let value = index.map(|i| given.remove(i).1);
+            if required && value.is_none() {
+                missing.push(name);
+            }
+            parsed.keywords.push((name, value));
+        }
+
+        if !missing.is_empty() {
+            let names = missing.iter().map(|name| _name(*name));
+            return Err(_keyword_error("missing", names));
+        }
+        if !given.is_empty() && !self.extra_keywords {
+            let names = given.iter().map(|(key, _)| _name(key.id()));
+            return Err(_keyword_error("unknown", names));
+        }
+        parsed.extra_keywords = given;
+        Ok(())
+    }
+
+    #[cold]
+    fn _arity_error(
+        &self,
+        given: usize,
+        kwargs: Option<Hash<Symbol, AnyObject>>,
+    ) -> AnyException {
+        let min = self.required + self.trailing;
+        let max = min + self.optional;
+
+        let mut message = format!("wrong number of arguments (given {}, expected {}", given, min);
+        if self.splat {
+            message.push('+');
+        } else if max != min {
+            let _ = write!(message, "..{}", max);
+        }
+
+        let missing: Vec<_> = self.keywords.iter()
+            .filter(|&&(name, required)| {
+                required && match kwargs {
+                    Some(kwargs) => kwargs.get(Symbol::from(name)).is_none(),
+                    None => true,
+                }
+            })
+            .map(|&(name, _)| _name(name))
+            .collect();
+        match missing.len() {
+            0 => {},
+            1 => { let _ = write!(message, "; required keyword: {}", missing[0]); },
+            _ => { let _ = write!(message, "; required keywords: {}", missing.join(", ")); },
+        }
+        message.push(')');
+
+        ArgumentError::new(message.as_str()).into_any_exception()
+    }
+}
+
+// Returns `obj` as a hash if it can be treated as keyword arguments
+#[inline]
+fn _keyword_hash(obj: AnyObject) -> Option<Hash<Symbol, AnyObject>> {
+    let hash = Hash::<AnyObject, AnyObject>::cast(obj)?;
+    let all_symbols = hash.try_for_each(|key, _| {
+        if key.is_symbol() { Ok(()) } else { Err(()) }
+    });
+    match all_symbols {
+        Ok(()) => unsafe { Some(Hash::cast_unchecked(hash)) },
+        Err(()) => None,
+    }
+}
+
+#[inline]
+fn _name(name: SymbolId) -> std::string::String {
+    name.name().to_string_lossy().into_owned()
+}
+
+#[cold]
+fn _keyword_error<I>(kind: &str, names: I) -> AnyException
+    where I: Iterator<Item = std::string::String>
+{
+    let names: Vec<_> = names.collect();
+    let message = format!(
+        "{} keyword{}: {}",
+        kind,
+        if names.len() == 1 { "" } else { "s" },
+        names.join(", "),
+    );
+    ArgumentError::new(message.as_str()).into_any_exception()
+}
@@ -1,4 +1,5 @@
 use crate::{
+    num::{AnyNumeric, Complex, Rational},
     prelude::*,
     string::Encoding,
     vm::InstrSeq,
@@ -35,14 +36,26 @@ impl<K: Object, V: Object> Classify for Hash<K, V> {
     }
 }
 
+impl<R: Rosy> Classify for RosyObject<R> {
+    #[inline]
+    fn class() -> Class<Self> {
+        unsafe { Class::cast_unchecked(R::class()) }
+    }
+}
+
 impl_trait! {
     AnyObject,    object;
     Class,        class;
     Module,       module;
     Integer,      integer;
+    Rational,     rational;
+    Complex,      complex;
+    AnyNumeric,   numeric;
     String,       string;
     Symbol,       symbol;
     Encoding,     encoding;
     AnyException, exception;
     InstrSeq,     instr_seq;
+    Proc,         proc;
+    Enumerator,   enumerator;
 }
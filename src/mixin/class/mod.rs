@@ -7,6 +7,7 @@ use std::{
     os::raw::c_int,
 };
 use crate::{
+    marshal::Marshal,
     mixin::{DefMixinError, MethodFn},
     object::{NonNullObject, Ty},
     prelude::*,
@@ -309,6 +310,89 @@ impl<O: Object> Class<O> {
         ))
     }
 
+    /// Creates a new instance from `args` and keyword arguments `kwargs`.
+    ///
+    /// See [`Object::call_with_kwargs`](trait.Object.html#method.call_with_kwargs)
+    /// for how `kwargs` is passed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # rosy::vm::init().unwrap();
+    /// # rosy::protected(|| {
+    /// use rosy::prelude::*;
+    ///
+    /// let keyword_init: Hash<Symbol, AnyObject> = vec![
+    ///     (Symbol::from("keyword_init"), AnyObject::from(true)),
+    /// ].into_iter().collect();
+    ///
+    /// let point = Class::get("Struct").unwrap()
+    ///     .new_instance_with_kwargs(&[Symbol::from("x")], keyword_init)
+    ///     .unwrap();
+    /// let point = point.to_class().unwrap();
+    ///
+    /// let x: Hash<Symbol, AnyObject> = vec![
+    ///     (Symbol::from("x"), AnyObject::from(1)),
+    /// ].into_iter().collect();
+    ///
+    /// let instance = point.new_instance_with_kwargs(&[] as &[AnyObject], x).unwrap();
+    /// assert_eq!(unsafe { instance.call("x") }, Integer::from(1));
+    /// # }).unwrap();
+    /// ```
+    #[inline]
+    pub fn new_instance_with_kwargs<A: Object>(
+        self,
+        args: &[A],
+        kwargs: Hash<Symbol, AnyObject>,
+    ) -> Result<O> {
+        // monomorphization
+        fn new_instance_with_kwargs(
+            c: Class,
+            a: &[AnyObject],
+            k: Hash<Symbol, AnyObject>,
+        ) -> Result<AnyObject> {
+            unsafe {
+                crate::protected_no_panic(|| {
+                    c.new_instance_with_kwargs_unchecked(a, k)
+                })
+            }
+        }
+        let class = self.into_any_class();
+        let args = AnyObject::convert_slice(args);
+        let object = new_instance_with_kwargs(class, args, kwargs)?;
+        unsafe { Ok(O::cast_unchecked(object)) }
+    }
+
+    /// Creates a new instance from `args` and keyword arguments `kwargs`.
+    ///
+    /// # Safety
+    ///
+    /// An exception may be thrown if the class expected different arguments.
+    #[inline]
+    pub unsafe fn new_instance_with_kwargs_unchecked<A: Object>(
+        self,
+        args: &[A],
+        kwargs: Hash<Symbol, AnyObject>,
+    ) -> O {
+        let args = AnyObject::convert_slice(args);
+        crate::object::_with_kwargs(args, kwargs, |args, _kw_splat| {
+            #[cfg(feature = "ruby_2_7")]
+            let raw = ruby::rb_class_new_instance_kw(
+                args.len() as c_int,
+                args.as_ptr() as *const ruby::VALUE,
+                self.raw(),
+                _kw_splat as c_int,
+            );
+            #[cfg(not(feature = "ruby_2_7"))]
+            let raw = ruby::rb_class_new_instance(
+                args.len() as c_int,
+                args.as_ptr() as *const ruby::VALUE,
+                self.raw(),
+            );
+            O::from_raw(raw)
+        })
+    }
+
     /// Returns the parent class of `self`.
     #[inline]
     pub fn superclass(self) -> Class {
@@ -357,26 +441,6 @@ impl<O: Object> Class<O> {
         unsafe { String::from_raw(ruby::rb_class_name(self.raw())) }
     }
 
-    // monomorphization
-    unsafe fn _def_method(
-        self,
-        name: SymbolId,
-        f: unsafe extern "C" fn() -> ruby::VALUE,
-        arity: c_int,
-    ) -> Result {
-        crate::protected_no_panic(|| self._def_method_unchecked(name, f, arity))
-    }
-
-    #[inline]
-    unsafe fn _def_method_unchecked(
-        self,
-        name: SymbolId,
-        f: unsafe extern "C" fn() -> ruby::VALUE,
-        arity: c_int,
-    ) {
-        ruby::rb_define_method_id(self.raw(), name.raw(), Some(f), arity)
-    }
-
     /// Defines a method for `name` on `self` that calls `f` when invoked.
     ///
     /// **Note:** This method can be unwieldy to use and so it is recommended to
@@ -497,7 +561,7 @@ impl<O: Object> Class<O> {
         N: Into<SymbolId>,
         F: MethodFn<O>,
     {
-        unsafe { self._def_method(name.into(), f.raw_fn(), F::ARITY) }
+        unsafe { super::_def_method(self.raw(), name.into().raw(), f.raw_fn(), F::ARITY) }
     }
 
     /// Defines a method for `name` on `self` that calls `f` when invoked.
@@ -520,7 +584,340 @@ impl<O: Object> Class<O> {
         N: Into<SymbolId>,
         F: MethodFn<O>,
     {
-        self._def_method_unchecked(name.into(), f.raw_fn(), F::ARITY)
+        super::_def_method_unchecked(self.raw(), name.into().raw(), f.raw_fn(), F::ARITY)
+    }
+}
+
+impl<R: Rosy> Class<RosyObject<R>> {
+    /// Makes Ruby allocate instances of `self` as `RosyObject<R>` with no data.
+    ///
+    /// Such an instance is [not initialized](struct.RosyObject.html#method.is_initialized)
+    /// and so can't be cast to `RosyObject<R>` until its data is set by
+    /// `initialize`. This is done automatically by
+    /// [`def_initialize`](#method.def_initialize).
+    #[inline]
+    pub fn def_alloc_func(self) {
+        RosyObject::<R>::_def_alloc_func(self.into_any_class());
+    }
+
+    /// Allows for creating instances of `self` from Ruby via `new`, with data
+    /// created by [`Initialize::initialize`](trait.Initialize.html#tymethod.initialize).
+    ///
+    /// This defines an allocation function and an `initialize` method. Calling
+    /// `initialize` again on an initialized instance raises a `NameError`.
+    ///
+    /// **Note:** the default [`Rosy::cast`](trait.Rosy.html#method.cast) only
+    /// succeeds for objects statically known to be `RosyObject<R>`, and so
+    /// `RosyObject::<R>::cast` returns `None` for instances created this way.
+    /// A manual `Rosy` implementation must override `cast` to call
+    /// [`RosyObject::cast_by_id`](struct.RosyObject.html#method.cast_by_id), as
+    /// done below. `#[derive(Rosy)]` does this automatically.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # rosy::vm::init().unwrap();
+    /// # rosy::protected(|| {
+    /// use std::{ffi::CStr, os::raw::c_char};
+    /// use rosy::{exception::ArgumentError, prelude::*};
+    ///
+    /// struct Celsius(f64);
+    ///
+    /// unsafe impl Rosy for Celsius {
+    ///     const ID: *const c_char = b"rosy_celsius\0".as_ptr() as _;
+    ///
+    ///     fn class() -> Class {
+    ///         Class::get_or_def("Celsius").unwrap()
+    ///     }
+    ///
+    ///     // Allows for casting instances created via `Celsius.new`
+    ///     fn cast<A: Object>(obj: A) -> Option<RosyObject<Self>> {
+    ///         unsafe { RosyObject::cast_by_id(obj) }
+    ///     }
+    ///
+    ///     fn mark(&self) {}
+    /// }
+    ///
+    /// impl Initialize for Celsius {
+    ///     fn initialize(args: &[AnyObject]) -> Result<Self> {
+    ///         if args.len() == 1 {
+    ///             Ok(Celsius(f64::from_ruby(&args[0])?))
+    ///         } else {
+    ///             Err(ArgumentError::new("expected degrees").into_any_exception())
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// Class::of::<RosyObject<Celsius>>().def_initialize().unwrap();
+    ///
+    /// let script = b"Celsius.new(21.5)\0";
+    /// let script = CStr::from_bytes_with_nul(script).unwrap();
+    ///
+    /// let object = unsafe { rosy::vm::eval(script) };
+    /// let celsius = RosyObject::<Celsius>::cast(object).unwrap();
+    /// assert_eq!(celsius.borrow().unwrap().0, 21.5);
+    /// # }).unwrap();
+    /// ```
+    pub fn def_initialize(self) -> Result where R: Initialize {
+        unsafe extern "C" fn initialize<R: Initialize>(
+            this: AnyObject,
+            argc: c_int,
+            argv: *const AnyObject,
+        ) -> AnyObject {
+            let args = crate::proc::_args(argc, argv as _);
+            crate::mixin::_call_method(|| {
+                RosyObject::<R>::_initialize(this, R::initialize(args)?)?;
+                Ok(AnyObject::nil())
+            })
+        }
+        let initialize: unsafe extern "C" fn(_, _, _) -> _ = initialize::<R>;
+
+        let class = self.into_any_class();
+        self.def_alloc_func();
+        class.def_method("initialize", initialize)
+    }
+
+    /// Defines `to_s` on `self` via the `Display` implementation of `R`.
+    pub fn def_to_s(self) -> Result where R: fmt::Display {
+        extern "C" fn to_s<R: Rosy + fmt::Display>(this: AnyObject) -> AnyObject {
+            crate::mixin::_call_method(|| {
+                let this = RosyObject::<R>::_cast_instance(this)?;
+                let string = this.borrow()?.to_string();
+                Ok(String::from(string.as_str()).into())
+            })
+        }
+        let to_s: extern "C" fn(_) -> _ = to_s::<R>;
+        self.into_any_class().def_method("to_s", to_s)
+    }
+
+    /// Defines `inspect` on `self` via the `Debug` implementation of `R`.
+    pub fn def_inspect(self) -> Result where R: fmt::Debug {
+        extern "C" fn inspect<R: Rosy + fmt::Debug>(this: AnyObject) -> AnyObject {
+            crate::mixin::_call_method(|| {
+                let this = RosyObject::<R>::_cast_instance(this)?;
+                let string = format!("{:?}", *this.borrow()?);
+                Ok(String::from(string.as_str()).into())
+            })
+        }
+        let inspect: extern "C" fn(_) -> _ = inspect::<R>;
+        self.into_any_class().def_method("inspect", inspect)
+    }
+
+    /// Defines `==` on `self` via the `PartialEq` implementation of `R`.
+    ///
+    /// Comparing against an object that isn't an instance of `R` returns
+    /// `false`.
+    pub fn def_eq(self) -> Result where R: PartialEq {
+        extern "C" fn eq<R: Rosy + PartialEq>(
+            this: AnyObject,
+            other: AnyObject,
+        ) -> AnyObject {
+            crate::mixin::_call_method(|| {
+                Ok(RosyObject::<R>::_data_eq(this, other)?.into())
+            })
+        }
+        let eq: extern "C" fn(_, _) -> _ = eq::<R>;
+        self.into_any_class().def_method("==", eq)
+    }
+
+    /// Defines `hash` and `eql?` on `self` via the `Hash` and `Eq`
+    /// implementations of `R`.
+    ///
+    /// This allows for instances of `self` to be used as keys in a Ruby
+    /// `Hash`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # rosy::vm::init().unwrap();
+    /// # rosy::protected(|| {
+    /// use std::os::raw::c_char;
+    /// use rosy::prelude::*;
+    ///
+    /// #[derive(PartialEq, Eq, Hash)]
+    /// struct Point(i32, i32);
+    ///
+    /// unsafe impl Rosy for Point {
+    ///     const ID: *const c_char = b"rosy_point\0".as_ptr() as _;
+    ///
+    ///     fn class() -> Class {
+    ///         Class::get_or_def("Point").unwrap()
+    ///     }
+    ///
+    ///     fn mark(&self) {}
+    /// }
+    ///
+    /// Class::of::<RosyObject<Point>>().def_hash().unwrap();
+    ///
+    /// let hash = Hash::<RosyObject<Point>, Integer>::new();
+    /// unsafe { hash.insert(RosyObject::from(Point(1, 2)), 3) };
+    ///
+    /// let value = hash.get(RosyObject::from(Point(1, 2)));
+    /// assert_eq!(value.unwrap(), 3);
+    /// assert!(hash.get(RosyObject::from(Point(2, 1))).is_none());
+    /// # }).unwrap();
+    /// ```
+    pub fn def_hash(self) -> Result where R: std::hash::Hash + Eq {
+        use std::{collections::hash_map::DefaultHasher, hash::Hasher};
+
+        extern "C" fn hash<R>(this: AnyObject) -> AnyObject
+            where R: Rosy + std::hash::Hash
+        {
+            crate::mixin::_call_method(|| {
+                let this = RosyObject::<R>::_cast_instance(this)?;
+                let mut hasher = DefaultHasher::new();
+                this.borrow()?.hash(&mut hasher);
+                Ok(Integer::from(hasher.finish() as i64).into())
+            })
+        }
+        let hash: extern "C" fn(_) -> _ = hash::<R>;
+
+        extern "C" fn eql<R: Rosy + Eq>(
+            this: AnyObject,
+            other: AnyObject,
+        ) -> AnyObject {
+            crate::mixin::_call_method(|| {
+                Ok(RosyObject::<R>::_data_eq(this, other)?.into())
+            })
+        }
+        let eql: extern "C" fn(_, _) -> _ = eql::<R>;
+
+        let class = self.into_any_class();
+        class.def_method("hash", hash)?;
+        class.def_method("eql?", eql)
+    }
+
+    /// Defines `<=>` on `self` via the `Ord` implementation of `R` and includes
+    /// the `Comparable` module.
+    ///
+    /// Comparing against an object that isn't an instance of `R` returns
+    /// `nil`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # rosy::vm::init().unwrap();
+    /// # rosy::protected(|| {
+    /// use std::os::raw::c_char;
+    /// use rosy::prelude::*;
+    ///
+    /// #[derive(PartialEq, Eq, PartialOrd, Ord)]
+    /// struct Version(u32, u32);
+    ///
+    /// unsafe impl Rosy for Version {
+    ///     const ID: *const c_char = b"rosy_version\0".as_ptr() as _;
+    ///
+    ///     fn class() -> Class {
+    ///         Class::get_or_def("Version").unwrap()
+    ///     }
+    ///
+    ///     fn mark(&self) {}
+    /// }
+    ///
+    /// Class::of::<RosyObject<Version>>().def_cmp().unwrap();
+    ///
+    /// let old = RosyObject::from(Version(1, 9));
+    /// let new = RosyObject::from(Version(2, 0));
+    ///
+    /// let array = Array::from_slice(&[new, old]);
+    /// let sorted = unsafe { array.call("sort") };
+    /// assert_eq!(sorted, Array::from_slice(&[old, new]));
+    ///
+    /// let is_less = unsafe { old.call_with("<", &[new]) };
+    /// assert!(is_less.is_true());
+    /// # }).unwrap();
+    /// ```
+    pub fn def_cmp(self) -> Result where R: Ord {
+        extern "C" fn cmp<R: Rosy + Ord>(
+            this: AnyObject,
+            other: AnyObject,
+        ) -> AnyObject {
+            crate::mixin::_call_method(|| {
+                let this = RosyObject::<R>::_cast_instance(this)?;
+                if !RosyObject::<R>::_is_instance(other) {
+                    return Ok(AnyObject::nil());
+                }
+                let other = unsafe { RosyObject::<R>::cast_unchecked(other) };
+                let order = if this.raw() == other.raw() {
+                    Ordering::Equal
+                } else {
+                    (*this.borrow()?).cmp(&*other.borrow()?)
+                };
+                Ok(Integer::from(order as i32).into())
+            })
+        }
+        let cmp: extern "C" fn(_, _) -> _ = cmp::<R>;
+
+        let class = self.into_any_class();
+        class.include(Module::comparable());
+        class.def_method("<=>", cmp)
+    }
+
+    /// Allows for copying instances of `self` via `dup` and `clone` using the
+    /// `Clone` implementation of `R`.
+    ///
+    /// This defines an allocation function and an `initialize_copy` method.
+    pub fn def_clone(self) -> Result where R: Clone {
+        extern "C" fn initialize_copy<R: Rosy + Clone>(
+            this: AnyObject,
+            orig: AnyObject,
+        ) -> AnyObject {
+            crate::mixin::_call_method(|| {
+                // Compares identities, since `this` is not yet initialized
+                if this.raw() != orig.raw() {
+                    let orig = RosyObject::<R>::_cast_instance(orig)?;
+                    let rosy = orig.borrow()?.clone();
+                    RosyObject::<R>::_initialize(this, rosy)?;
+                }
+                Ok(this)
+            })
+        }
+        let initialize_copy: extern "C" fn(_, _) -> _ = initialize_copy::<R>;
+
+        let class = self.into_any_class();
+        self.def_alloc_func();
+        class.def_method("initialize_copy", initialize_copy)
+    }
+
+    /// Allows for instances of `self` to be marshalled by Ruby via the
+    /// [`Marshal`](marshal/trait.Marshal.html) implementation of `R`.
+    ///
+    /// This defines a `_dump` method and a `_load` singleton method.
+    pub fn def_marshal(self) -> Result where R: Marshal {
+        extern "C" fn dump<R: Marshal>(
+            this: AnyObject,
+            _level: AnyObject,
+        ) -> AnyObject {
+            crate::mixin::_call_method(|| {
+                let this = RosyObject::<R>::_cast_instance(this)?;
+                let bytes = this.borrow()?.dump()?;
+                Ok(String::from(&bytes[..]).into())
+            })
+        }
+        let dump: extern "C" fn(_, _) -> _ = dump::<R>;
+
+        extern "C" fn load<R: Marshal>(
+            class: Class,
+            string: AnyObject,
+        ) -> AnyObject {
+            crate::mixin::_call_method(|| {
+                let string = String::from_ruby(&string)?;
+                let rosy = R::load(unsafe { string.as_bytes() })?;
+                // `_load` is inherited, so the receiver may be a subclass
+                let object = if class.inherits(R::class()) {
+                    // Safe because the methods of `class` expect `R`
+                    unsafe { RosyObject::with_class(rosy, class) }
+                } else {
+                    RosyObject::from(rosy)
+                };
+                Ok(object.into())
+            })
+        }
+        let load: extern "C" fn(_, _) -> _ = load::<R>;
+
+        let class = self.into_any_class();
+        class.def_method("_dump", dump)?;
+        class.def_singleton_method("_load", load)
     }
 }
 
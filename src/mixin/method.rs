@@ -20,7 +20,7 @@ pub unsafe trait MethodFn<Receiver: Object> {
     fn raw_fn(self) -> unsafe extern "C" fn() -> VALUE;
 }
 
-/// Defines a method on a [`Class`] instance in a simple manner.
+/// Defines a method on a [`Class`] or [`Module`] instance in a simple manner.
 ///
 /// This is purely a convenience wrapper for [`def_method`] that makes the
 /// process much less painful and tedious.
@@ -71,8 +71,67 @@ pub unsafe trait MethodFn<Receiver: Object> {
 /// # }).unwrap();
 /// ```
 ///
-/// The same types supported in [`def_method`] are supported here via explicit
-/// type annotations:
+/// Arguments may be of any type that implements [`FromRuby`] and the body may
+/// evaluate to any type that implements [`IntoRuby`]. The return type can
+/// optionally be specified when the body is a block:
+///
+/// ```rust,edition2018
+/// # rosy::vm::init().unwrap();
+/// # rosy::protected(|| {
+/// use rosy::prelude::*;
+///
+/// let class = Class::object();
+///
+/// rosy::def_method!(class, "repeat_str", |_this, s: &str, n: usize| -> Option<std::string::String> {
+///     if n == 0 {
+///         None
+///     } else {
+///         Some(s.repeat(n))
+///     }
+/// }).unwrap();
+///
+/// let object = AnyObject::nil();
+/// let output = unsafe {
+///     object.call_with("repeat_str", &[AnyObject::from("ab"), AnyObject::from(3)])
+/// };
+///
+/// assert_eq!(output, String::from("ababab"));
+/// # }).unwrap();
+/// ```
+///
+/// If an argument fails to convert, a `TypeError` is raised. Likewise, if the
+/// body evaluates to `Err(exception)`, then `exception` is raised:
+///
+/// ```rust,edition2018
+/// # rosy::vm::init().unwrap();
+/// # rosy::protected(|| {
+/// use rosy::{exception::ArgumentError, prelude::*};
+///
+/// let class = Class::object();
+///
+/// rosy::def_method!(class, "checked_half", |_this, n: i64| {
+///     if n % 2 == 0 {
+///         Ok(n / 2)
+///     } else {
+///         Err(ArgumentError::new("odd number"))
+///     }
+/// }).unwrap();
+///
+/// let object = AnyObject::nil();
+/// let result = unsafe {
+///     object.call_with_protected("checked_half", &[AnyObject::from("4")])
+/// };
+/// assert!(result.unwrap_err().is_type_error());
+///
+/// let result = unsafe {
+///     object.call_with_protected("checked_half", &[AnyObject::from(3)])
+/// };
+/// assert!(result.unwrap_err().is_arg_error());
+/// # }).unwrap();
+/// ```
+///
+/// A single argument declared as `args: Array` will instead collect all given
+/// arguments into an [`Array`], allowing for a variable number of them:
 ///
 /// ```rust,edition2018
 /// # rosy::vm::init().unwrap();
@@ -95,10 +154,199 @@ pub unsafe trait MethodFn<Receiver: Object> {
 /// # }).unwrap();
 /// ```
 ///
+/// This is matched by the literal `Array` token, so an alias or a path like
+/// `rosy::Array` is converted from a single argument instead. The `*args` form
+/// is equivalent and doesn't depend on the spelling:
+///
+/// ```rust,edition2018
+/// # rosy::vm::init().unwrap();
+/// # rosy::protected(|| {
+/// use rosy::prelude::*;
+///
+/// let class = Class::object();
+///
+/// rosy::def_method!(class, "count_all", |_this, *args| {
+///     args.len() as i64
+/// }).unwrap();
+///
+/// let object = AnyObject::nil();
+/// let output = unsafe {
+///     object.call_with("count_all", &[AnyObject::nil(), AnyObject::nil()])
+/// };
+///
+/// assert_eq!(output, Integer::from(2));
+/// # }).unwrap();
+/// ```
+///
+/// Arguments can also be taken as a count and a pointer to their start, with
+/// the signature used as-is. The body must be a block in this case:
+///
+/// ```rust,edition2018
+/// # rosy::vm::init().unwrap();
+/// # rosy::protected(|| {
+/// use std::os::raw::c_int;
+/// use rosy::prelude::*;
+///
+/// let class = Class::object();
+///
+/// rosy::def_method!(class, "count_args", |_this, argc: c_int, _argv: *const AnyObject| {
+///     argc as i64
+/// }).unwrap();
+///
+/// let object = AnyObject::nil();
+/// let output = unsafe {
+///     object.call_with("count_args", &[AnyObject::nil(), AnyObject::nil()])
+/// };
+///
+/// assert_eq!(output, Integer::from(2));
+/// # }).unwrap();
+/// ```
+///
+/// Methods can also be defined on a [`Module`], in which case the receiver
+/// is always an [`AnyObject`]:
+///
+/// ```rust,edition2018
+/// # rosy::vm::init().unwrap();
+/// # rosy::protected(|| {
+/// use rosy::prelude::*;
+///
+/// let module = Module::def("Describe").unwrap();
+///
+/// rosy::def_method!(module, "describe", |this| {
+///     this.inspect()
+/// }).unwrap();
+///
+/// Class::array().include(module);
+///
+/// let array: Array = (0..3).collect();
+/// let output = unsafe { array.call("describe") };
+///
+/// assert_eq!(output, array.inspect());
+/// # }).unwrap();
+/// ```
+///
+/// [`AnyObject`]: struct.AnyObject.html
+/// [`Array`]: struct.Array.html
 /// [`Class`]: struct.Class.html
+/// [`FromRuby`]: trait.FromRuby.html
+/// [`IntoRuby`]: trait.IntoRuby.html
+/// [`Module`]: struct.Module.html
 /// [`def_method`]: struct.Class.html#method.def_method
 #[macro_export]
 macro_rules! def_method {
+    (
+        $class:expr,
+        $name:expr,
+        |
+            $this:ident $(: $this_ty:ty)?,
+            $argc:ident : $argc_ty:ty,
+            $argv:ident : *const $argv_ty:ty
+            $(,)?
+        |
+        $(-> $ret:ty)?
+        $body:block
+    ) => {
+        $crate::_def_method!(
+            def_method, $class, $name,
+            $this [$($this_ty)?],
+            raw [$argc : $argc_ty, $argv : $argv_ty],
+            [$($ret)?],
+            $body
+        )
+    };
+    (
+        $class:expr,
+        $name:expr,
+        |
+            $this:ident $(: $this_ty:ty)?,
+            $args:ident : Array
+            $(,)?
+        |
+        $(-> $ret:ty)?
+        $body:block
+    ) => {
+        $crate::_def_method!(
+            def_method, $class, $name,
+            $this [$($this_ty)?],
+            variadic $args,
+            [$($ret)?],
+            $body
+        )
+    };
+    (
+        $class:expr,
+        $name:expr,
+        |
+            $this:ident $(: $this_ty:ty)?,
+            $args:ident : Array
+            $(,)?
+        |
+        $body:expr
+    ) => {
+        $crate::_def_method!(
+            def_method, $class, $name,
+            $this [$($this_ty)?],
+            variadic $args,
+            [],
+            $body
+        )
+    };
+    (
+        $class:expr,
+        $name:expr,
+        |
+            $this:ident $(: $this_ty:ty)?,
+            * $args:ident
+            $(,)?
+        |
+        -> $ret:ty
+        $body:block
+    ) => {
+        $crate::_def_method!(
+            def_method, $class, $name,
+            $this [$($this_ty)?],
+            variadic $args,
+            [$ret],
+            $body
+        )
+    };
+    (
+        $class:expr,
+        $name:expr,
+        |
+            $this:ident $(: $this_ty:ty)?,
+            * $args:ident
+            $(,)?
+        |
+        $body:expr
+    ) => {
+        $crate::_def_method!(
+            def_method, $class, $name,
+            $this [$($this_ty)?],
+            variadic $args,
+            [],
+            $body
+        )
+    };
+    (
+        $class:expr,
+        $name:expr,
+        |
+                $this:ident $(: $this_ty:ty)?
+            $(, $args:ident $(: $args_ty:ty)?)*
+            $(,)?
+        |
+        -> $ret:ty
+        $body:block
+    ) => {
+        $crate::_def_method!(
+            def_method, $class, $name,
+            $this [$($this_ty)?],
+            [$($args [$($args_ty)?])*],
+            [$ret],
+            $body
+        )
+    };
     (
         $class:expr,
         $name:expr,
@@ -108,9 +356,98 @@ macro_rules! def_method {
             $(,)?
         |
         $body:expr
+    ) => {
+        $crate::_def_method!(
+            def_method, $class, $name,
+            $this [$($this_ty)?],
+            [$($args [$($args_ty)?])*],
+            [],
+            $body
+        )
+    };
+}
+
+// The implementation of `def_method!` and `def_method_unchecked!`, where `$def`
+// is the method used to define `_method` on `$class`
+#[doc(hidden)]
+#[macro_export]
+macro_rules! _def_method {
+    (
+        $def:ident, $class:expr, $name:expr,
+        $this:ident [$($this_ty:ty)?],
+        raw [$argc:ident : $argc_ty:ty, $argv:ident : $argv_ty:ty],
+        [$($ret:ty)?],
+        $body:tt
+    ) => { {
+        type __AnyObject = $crate::AnyObject;
+
+        macro_rules! _substitute_any_object {
+            () => { __AnyObject };
+            ($__t:ty) => { $__t };
+        }
+        macro_rules! _cast_class {
+            ($c:expr,) => { $crate::mixin::_DefMethodTarget::_into_any($c) };
+            ($c:expr, $_t:ty) => { $c };
+        }
+
+        // The signature is passed through as-is and checked via `MethodFn`
+        extern "C" fn _method(
+            $this : _substitute_any_object!($($this_ty)?),
+            $argc : $argc_ty,
+            $argv : *const $argv_ty,
+        ) -> __AnyObject {
+            $crate::mixin::_call_method(|| {
+                let __output $(: $ret)? = $body;
+                $crate::mixin::_MethodOutput::_into_result(__output)
+            })
+        }
+
+        let _method: extern "C" fn(_, _, _) -> _ = _method;
+
+        let _class = _cast_class!($class, $($this_ty)?);
+        _class.$def($name, _method)
+    } };
+    (
+        $def:ident, $class:expr, $name:expr,
+        $this:ident [$($this_ty:ty)?],
+        variadic $args:ident,
+        [$($ret:ty)?],
+        $body:tt
+    ) => { {
+        type __AnyObject = $crate::AnyObject;
+
+        macro_rules! _substitute_any_object {
+            () => { __AnyObject };
+            ($__t:ty) => { $__t };
+        }
+        macro_rules! _cast_class {
+            ($c:expr,) => { $crate::mixin::_DefMethodTarget::_into_any($c) };
+            ($c:expr, $_t:ty) => { $c };
+        }
+
+        extern "C" fn _method(
+            $this : _substitute_any_object!($($this_ty)?),
+            $args : $crate::Array,
+        ) -> __AnyObject {
+            $crate::mixin::_call_method(|| {
+                let __output $(: $ret)? = $body;
+                $crate::mixin::_MethodOutput::_into_result(__output)
+            })
+        }
+
+        let _method: extern "C" fn(_, $crate::Array) -> _ = _method;
+
+        let _class = _cast_class!($class, $($this_ty)?);
+        _class.$def($name, _method)
+    } };
+    (
+        $def:ident, $class:expr, $name:expr,
+        $this:ident [$($this_ty:ty)?],
+        [$($args:ident [$($args_ty:ty)?])*],
+        [$($ret:ty)?],
+        $body:tt
     ) => { {
         type __AnyObject = $crate::AnyObject;
-        type __Class = $crate::Class;
 
         macro_rules! _replace {
             ($__t:tt $sub:tt) => { $sub }
@@ -120,24 +457,35 @@ macro_rules! def_method {
             ($__t:ty) => { $__t };
         }
         macro_rules! _cast_class {
-            ($c:expr,) => { __Class::into_any_class($c) };
+            ($c:expr,) => { $crate::mixin::_DefMethodTarget::_into_any($c) };
             ($c:expr, $_t:ty) => { $c };
         }
 
+        // Arguments are taken as `AnyObject` and then converted via `FromRuby`
         extern "C" fn _method(
                $this : _substitute_any_object!($($this_ty)?),
-            $( $args : _substitute_any_object!($($args_ty)?) ),*
-        ) -> AnyObject { $body.into() }
+            $( $args : __AnyObject ),*
+        ) -> __AnyObject {
+            $crate::mixin::_call_method(|| {
+                $(
+                    let $args: _substitute_any_object!($($args_ty)?) =
+                        $crate::FromRuby::from_ruby(&$args)?;
+                )*
+                let __output $(: $ret)? = $body;
+                $crate::mixin::_MethodOutput::_into_result(__output)
+            })
+        }
 
         let _method: extern "C" fn(_, $( _replace!($args _) ),*) -> _ = _method;
 
         let _class = _cast_class!($class, $($this_ty)?);
-        $crate::Class::def_method(_class, $name, _method)
+        _class.$def($name, _method)
     } };
 }
 
-/// Defines a method on a [`Class`](struct.Class.html) instance in a simple
-/// manner, without checking for exceptions.
+/// Defines a method on a [`Class`](struct.Class.html) or
+/// [`Module`](struct.Module.html) instance in a simple manner, without checking
+/// for exceptions.
 ///
 /// This is purely a convenience wrapper for
 /// [`def_method_unchecked`](struct.Class.html#method.def_method_unchecked) that
@@ -151,6 +499,119 @@ macro_rules! def_method {
 /// exception will be raised.
 #[macro_export]
 macro_rules! def_method_unchecked {
+    (
+        $class:expr,
+        $name:expr,
+        |
+            $this:ident $(: $this_ty:ty)?,
+            $argc:ident : $argc_ty:ty,
+            $argv:ident : *const $argv_ty:ty
+            $(,)?
+        |
+        $(-> $ret:ty)?
+        $body:block
+    ) => {
+        $crate::_def_method!(
+            def_method_unchecked, $class, $name,
+            $this [$($this_ty)?],
+            raw [$argc : $argc_ty, $argv : $argv_ty],
+            [$($ret)?],
+            $body
+        )
+    };
+    (
+        $class:expr,
+        $name:expr,
+        |
+            $this:ident $(: $this_ty:ty)?,
+            $args:ident : Array
+            $(,)?
+        |
+        $(-> $ret:ty)?
+        $body:block
+    ) => {
+        $crate::_def_method!(
+            def_method_unchecked, $class, $name,
+            $this [$($this_ty)?],
+            variadic $args,
+            [$($ret)?],
+            $body
+        )
+    };
+    (
+        $class:expr,
+        $name:expr,
+        |
+            $this:ident $(: $this_ty:ty)?,
+            $args:ident : Array
+            $(,)?
+        |
+        $body:expr
+    ) => {
+        $crate::_def_method!(
+            def_method_unchecked, $class, $name,
+            $this [$($this_ty)?],
+            variadic $args,
+            [],
+            $body
+        )
+    };
+    (
+        $class:expr,
+        $name:expr,
+        |
+            $this:ident $(: $this_ty:ty)?,
+            * $args:ident
+            $(,)?
+        |
+        -> $ret:ty
+        $body:block
+    ) => {
+        $crate::_def_method!(
+            def_method_unchecked, $class, $name,
+            $this [$($this_ty)?],
+            variadic $args,
+            [$ret],
+            $body
+        )
+    };
+    (
+        $class:expr,
+        $name:expr,
+        |
+            $this:ident $(: $this_ty:ty)?,
+            * $args:ident
+            $(,)?
+        |
+        $body:expr
+    ) => {
+        $crate::_def_method!(
+            def_method_unchecked, $class, $name,
+            $this [$($this_ty)?],
+            variadic $args,
+            [],
+            $body
+        )
+    };
+    (
+        $class:expr,
+        $name:expr,
+        |
+                $this:ident $(: $this_ty:ty)?
+            $(, $args:ident $(: $args_ty:ty)?)*
+            $(,)?
+        |
+        -> $ret:ty
+        $body:block
+    ) => {
+        $crate::_def_method!(
+            def_method_unchecked, $class, $name,
+            $this [$($this_ty)?],
+            [$($args [$($args_ty)?])*],
+            [$ret],
+            $body
+        )
+    };
     (
         $class:expr,
         $name:expr,
@@ -160,32 +621,128 @@ macro_rules! def_method_unchecked {
             $(,)?
         |
         $body:expr
-    ) => { {
-        type __AnyObject = $crate::AnyObject;
-        type __Class = $crate::Class;
+    ) => {
+        $crate::_def_method!(
+            def_method_unchecked, $class, $name,
+            $this [$($this_ty)?],
+            [$($args [$($args_ty)?])*],
+            [],
+            $body
+        )
+    };
+}
 
-        macro_rules! _replace {
-            ($__t:tt $sub:tt) => { $sub }
-        }
-        macro_rules! _substitute_any_object {
-            () => { __AnyObject };
-            ($__t:ty) => { $__t };
-        }
-        macro_rules! _cast_class {
-            ($c:expr,) => { __Class::into_any_class($c) };
-            ($c:expr, $_t:ty) => { $c };
-        }
+// Allows `def_method!` to take either a `Class` or a `Module` when the type of
+// `this` isn't specified
+#[doc(hidden)]
+pub trait _DefMethodTarget {
+    type Target;
 
-        extern "C" fn _method(
-               $this : _substitute_any_object!($($this_ty)?),
-            $( $args : _substitute_any_object!($($args_ty)?) ),*
-        ) -> AnyObject { $body.into() }
+    fn _into_any(self) -> Self::Target;
+}
 
-        let _method: extern "C" fn(_, $( _replace!($args _) ),*) -> _ = _method;
+impl<O: Object> _DefMethodTarget for Class<O> {
+    type Target = Class;
 
-        let _class = _cast_class!($class, $($this_ty)?);
-        $crate::Class::def_method_unchecked(_class, $name, _method)
-    } };
+    #[inline]
+    fn _into_any(self) -> Class { self.into_any_class() }
+}
+
+impl _DefMethodTarget for Module {
+    type Target = Module;
+
+    #[inline]
+    fn _into_any(self) -> Module { self }
+}
+
+// Converts the output of a `def_method!` body into the method's return value
+#[doc(hidden)]
+pub trait _MethodOutput {
+    fn _into_result(self) -> Result<AnyObject>;
+}
+
+impl<T: IntoRuby> _MethodOutput for T {
+    #[inline]
+    fn _into_result(self) -> Result<AnyObject> {
+        Ok(self.into_ruby())
+    }
+}
+
+impl<T: IntoRuby, E: Exception> _MethodOutput for Result<T, E> {
+    #[inline]
+    fn _into_result(self) -> Result<AnyObject> {
+        self.map(IntoRuby::into_ruby).map_err(Exception::into_any_exception)
+    }
+}
+
+// Converts the output of `new` in a `#[rosy::class]` impl into the data of the
+// created instance
+#[doc(hidden)]
+pub trait _NewOutput<R> {
+    fn _into_result(self) -> Result<R>;
+}
+
+impl<R: Rosy> _NewOutput<R> for R {
+    #[inline]
+    fn _into_result(self) -> Result<R> {
+        Ok(self)
+    }
+}
+
+impl<R: Rosy, E: Exception> _NewOutput<R> for Result<R, E> {
+    #[inline]
+    fn _into_result(self) -> Result<R> {
+        self.map_err(Exception::into_any_exception)
+    }
+}
+
+// Gets or defines the class for a `#[rosy::class]` impl under `parent`
+//
+// Instances are allocated without data for `initialize` to set if `R` has a
+// `new` function; otherwise they can only be created from Rust
+#[doc(hidden)]
+pub fn _def_rosy_class<R: Rosy>(
+    parent: impl Mixin,
+    name: &str,
+    has_new: bool,
+) -> Result<Class> {
+    use crate::exception::{FrozenError, TypeError};
+    use super::DefMixinError::*;
+
+    let class = match parent.def_class(name) {
+        Ok(class) | Err(ExistingClass(class)) => class,
+        Err(ExistingModule(_)) | Err(ExistingConst(_)) => {
+            let message = format!("{} is not a class", name);
+            return Err(TypeError::new(message.as_str()).into_any_exception());
+        },
+        Err(FrozenClass(_)) | Err(FrozenModule(_)) => {
+            let message = format!("can't define {} in a frozen namespace", name);
+            return Err(FrozenError::new(message.as_str()).into_any_exception());
+        },
+    };
+    if has_new {
+        RosyObject::<R>::_def_alloc_func(class);
+    } else {
+        unsafe { crate::ruby::rb_undef_alloc_func(class.raw()) };
+    }
+    Ok(class)
+}
+
+// Calls the body of a `def_method!` method, raising any returned exception and
+// propagating any panic as an exception
+#[doc(hidden)]
+#[inline]
+pub fn _call_method<F>(f: F) -> AnyObject
+    where F: FnOnce() -> Result<AnyObject>
+{
+    let result = crate::protected::_catch_jump(f);
+    unsafe {
+        match result {
+            Ok(Ok(value)) => value,
+            Ok(Err(exception)) => exception.raise(),
+            Err(jump) => jump.resume(),
+        }
+    }
 }
 
 macro_rules! impl_trait {
@@ -1,5 +1,6 @@
 //! Ruby mixins.
 
+use std::os::raw::c_int;
 use crate::{
     prelude::*,
     ruby::{self, ID, VALUE},
@@ -7,10 +8,11 @@ use crate::{
     vm::EvalArgs,
 };
 
+mod args;
 mod class;
 mod method;
 mod module;
-pub use self::{class::*, method::*, module::*};
+pub use self::{args::*, class::*, method::*, module::*};
 
 #[inline]
 fn _get_const(m: impl Mixin, name: SymbolId) -> Option<AnyObject> {
@@ -28,6 +30,74 @@ unsafe fn _get_const_unchecked(m: impl Mixin, name: impl Into<SymbolId>) -> AnyO
     AnyObject::from_raw(ruby::rb_const_get(m.raw(), name.into().raw()))
 }
 
+// monomorphization
+unsafe fn _def_method(
+    m: VALUE,
+    name: ID,
+    f: unsafe extern "C" fn() -> VALUE,
+    arity: c_int,
+) -> Result {
+    crate::protected_no_panic(|| _def_method_unchecked(m, name, f, arity))
+}
+
+#[inline]
+unsafe fn _def_method_unchecked(
+    m: VALUE,
+    name: ID,
+    f: unsafe extern "C" fn() -> VALUE,
+    arity: c_int,
+) {
+    ruby::rb_define_method_id(m, name, Some(f), arity)
+}
+
+type DefMethodFn = unsafe extern "C" fn(
+    VALUE,
+    *const std::os::raw::c_char,
+    Option<unsafe extern "C" fn() -> VALUE>,
+    c_int,
+);
+
+// monomorphization
+unsafe fn _def_method_with(
+    def: DefMethodFn,
+    m: VALUE,
+    name: SymbolId,
+    f: unsafe extern "C" fn() -> VALUE,
+    arity: c_int,
+) -> Result {
+    crate::protected_no_panic(|| _def_method_with_unchecked(def, m, name, f, arity))
+}
+
+#[inline]
+unsafe fn _def_method_with_unchecked(
+    def: DefMethodFn,
+    m: VALUE,
+    name: SymbolId,
+    f: unsafe extern "C" fn() -> VALUE,
+    arity: c_int,
+) {
+    def(m, name.name().as_ptr(), Some(f), arity)
+}
+
+// monomorphization
+unsafe fn _modify_method(
+    modify: unsafe extern "C" fn(VALUE, ID),
+    m: VALUE,
+    name: ID,
+) -> Result {
+    crate::protected_no_panic(|| modify(m, name))
+}
+
+// Calls a query method like `method_defined?` on `m` with `name`
+#[inline]
+fn _query_method(m: VALUE, query: &str, name: SymbolId) -> bool {
+    let query = SymbolId::from(query).raw();
+    let name = Symbol::from(name).raw();
+    unsafe {
+        ruby::rb_funcallv(m, query, 1, &name) == crate::util::TRUE_VALUE
+    }
+}
+
 // monomorphization
 unsafe fn _set_attr(m: VALUE, name: ID, read: bool, write: bool) -> Result {
     crate::protected_no_panic(|| _set_attr_unchecked(m, name, read, write))
@@ -310,6 +380,268 @@ pub trait Mixin: Object + Sealed {
         _set_attr_unchecked(self.raw(), name.into().raw(), true, true);
     }
 
+    /// Defines a private method for `name` on `self` that calls `f` when
+    /// invoked.
+    ///
+    /// See [`Class::def_method`](struct.Class.html#method.def_method) for more
+    /// info on `MethodFn`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # rosy::vm::init().unwrap();
+    /// use rosy::prelude::*;
+    ///
+    /// extern "C" fn secret(_this: AnyObject) -> Integer {
+    ///     Integer::from(42)
+    /// }
+    /// let secret: extern fn(_) -> _ = secret;
+    ///
+    /// let class = Class::def("Secretive").unwrap();
+    /// class.def_private_method("secret", secret).unwrap();
+    ///
+    /// assert!(class.has_private_method("secret"));
+    /// assert!(!class.has_public_method("secret"));
+    ///
+    /// let object = class.new_instance().unwrap();
+    /// let error = unsafe { object.call_public_protected("secret") };
+    /// assert!(error.unwrap_err().is_no_method_error());
+    /// ```
+    #[inline]
+    fn def_private_method<N, F>(self, name: N, f: F) -> Result
+    where
+        N: Into<SymbolId>,
+        F: MethodFn<AnyObject>,
+    {
+        let def = ruby::rb_define_private_method;
+        unsafe {
+            _def_method_with(def, self.raw(), name.into(), f.raw_fn(), F::ARITY)
+        }
+    }
+
+    /// Defines a private method for `name` on `self` that calls `f` when
+    /// invoked.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `self` is not frozen or else a `FrozenError`
+    /// exception will be raised.
+    #[inline]
+    unsafe fn def_private_method_unchecked<N, F>(self, name: N, f: F)
+    where
+        N: Into<SymbolId>,
+        F: MethodFn<AnyObject>,
+    {
+        let def = ruby::rb_define_private_method;
+        _def_method_with_unchecked(def, self.raw(), name.into(), f.raw_fn(), F::ARITY)
+    }
+
+    /// Defines a protected method for `name` on `self` that calls `f` when
+    /// invoked.
+    ///
+    /// See [`Class::def_method`](struct.Class.html#method.def_method) for more
+    /// info on `MethodFn`.
+    #[inline]
+    fn def_protected_method<N, F>(self, name: N, f: F) -> Result
+    where
+        N: Into<SymbolId>,
+        F: MethodFn<AnyObject>,
+    {
+        let def = ruby::rb_define_protected_method;
+        unsafe {
+            _def_method_with(def, self.raw(), name.into(), f.raw_fn(), F::ARITY)
+        }
+    }
+
+    /// Defines a protected method for `name` on `self` that calls `f` when
+    /// invoked.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `self` is not frozen or else a `FrozenError`
+    /// exception will be raised.
+    #[inline]
+    unsafe fn def_protected_method_unchecked<N, F>(self, name: N, f: F)
+    where
+        N: Into<SymbolId>,
+        F: MethodFn<AnyObject>,
+    {
+        let def = ruby::rb_define_protected_method;
+        _def_method_with_unchecked(def, self.raw(), name.into(), f.raw_fn(), F::ARITY)
+    }
+
+    /// Makes `new_name` refer to a copy of the method `old_name` in `self`.
+    ///
+    /// This is equivalent to the `alias_method` method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # rosy::vm::init().unwrap();
+    /// use rosy::prelude::*;
+    ///
+    /// let class = Class::def("Aliased").unwrap();
+    /// class.alias_method("text", "to_s").unwrap();
+    ///
+    /// let object = class.new_instance().unwrap();
+    /// let text = unsafe { object.call("text") };
+    ///
+    /// assert_eq!(text, object.to_s());
+    /// ```
+    ///
+    /// Aliasing a method that doesn't exist will result in a `NameError`:
+    ///
+    /// ```
+    /// # rosy::vm::init().unwrap();
+    /// use rosy::prelude::*;
+    ///
+    /// let class = Class::def("BadAlias").unwrap();
+    /// let error = class.alias_method("a", "does_not_exist").unwrap_err();
+    ///
+    /// assert!(error.is_name_error());
+    /// ```
+    #[inline]
+    fn alias_method<N, O>(self, new_name: N, old_name: O) -> Result
+    where
+        N: Into<SymbolId>,
+        O: Into<SymbolId>,
+    {
+        // monomorphization
+        fn alias_method(m: VALUE, new_name: ID, old_name: ID) -> Result {
+            unsafe {
+                crate::protected_no_panic(|| ruby::rb_alias(m, new_name, old_name))
+            }
+        }
+        alias_method(self.raw(), new_name.into().raw(), old_name.into().raw())
+    }
+
+    /// Makes `new_name` refer to a copy of the method `old_name` in `self`.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `self` is not frozen and that `old_name` is
+    /// defined or else an exception will be raised.
+    #[inline]
+    unsafe fn alias_method_unchecked<N, O>(self, new_name: N, old_name: O)
+    where
+        N: Into<SymbolId>,
+        O: Into<SymbolId>,
+    {
+        ruby::rb_alias(self.raw(), new_name.into().raw(), old_name.into().raw())
+    }
+
+    /// Prevents `self` from responding to calls to the method `name`, even if
+    /// it is defined by an ancestor.
+    ///
+    /// This is equivalent to the `undef_method` method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # rosy::vm::init().unwrap();
+    /// use rosy::prelude::*;
+    ///
+    /// let class = Class::def("Undefined").unwrap();
+    /// class.undef_method("to_s").unwrap();
+    ///
+    /// let object = class.new_instance().unwrap();
+    /// let error = unsafe { object.call_protected("to_s") };
+    ///
+    /// assert!(error.unwrap_err().is_no_method_error());
+    /// ```
+    #[inline]
+    fn undef_method(self, name: impl Into<SymbolId>) -> Result {
+        unsafe { _modify_method(ruby::rb_undef, self.raw(), name.into().raw()) }
+    }
+
+    /// Prevents `self` from responding to calls to the method `name`, even if
+    /// it is defined by an ancestor.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `self` is not frozen and that `name` is
+    /// defined or else an exception will be raised.
+    #[inline]
+    unsafe fn undef_method_unchecked(self, name: impl Into<SymbolId>) {
+        ruby::rb_undef(self.raw(), name.into().raw())
+    }
+
+    /// Removes the method `name` from `self`, allowing calls to fall back to
+    /// an ancestor's definition.
+    ///
+    /// This is equivalent to the `remove_method` method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # rosy::vm::init().unwrap();
+    /// use rosy::prelude::*;
+    ///
+    /// let class = Class::def("Removed").unwrap();
+    /// class.alias_method("text", "to_s").unwrap();
+    /// assert!(class.has_method("text"));
+    ///
+    /// class.remove_method("text").unwrap();
+    /// assert!(!class.has_method("text"));
+    ///
+    /// // `to_s` is defined by an ancestor and thus can't be removed
+    /// let error = class.remove_method("to_s").unwrap_err();
+    /// assert!(error.is_name_error());
+    /// ```
+    #[inline]
+    fn remove_method(self, name: impl Into<SymbolId>) -> Result {
+        let name = name.into().raw();
+        unsafe { _modify_method(ruby::rb_remove_method_id, self.raw(), name) }
+    }
+
+    /// Removes the method `name` from `self`, allowing calls to fall back to
+    /// an ancestor's definition.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `self` is not frozen and that `name` is
+    /// defined directly in `self` or else an exception will be raised.
+    #[inline]
+    unsafe fn remove_method_unchecked(self, name: impl Into<SymbolId>) {
+        ruby::rb_remove_method_id(self.raw(), name.into().raw())
+    }
+
+    /// Returns whether a public or protected method `name` is defined by
+    /// `self` or its ancestors.
+    ///
+    /// This is equivalent to the `method_defined?` method.
+    #[inline]
+    fn has_method(self, name: impl Into<SymbolId>) -> bool {
+        _query_method(self.raw(), "method_defined?", name.into())
+    }
+
+    /// Returns whether a public method `name` is defined by `self` or its
+    /// ancestors.
+    ///
+    /// This is equivalent to the `public_method_defined?` method.
+    #[inline]
+    fn has_public_method(self, name: impl Into<SymbolId>) -> bool {
+        _query_method(self.raw(), "public_method_defined?", name.into())
+    }
+
+    /// Returns whether a private method `name` is defined by `self` or its
+    /// ancestors.
+    ///
+    /// This is equivalent to the `private_method_defined?` method.
+    #[inline]
+    fn has_private_method(self, name: impl Into<SymbolId>) -> bool {
+        _query_method(self.raw(), "private_method_defined?", name.into())
+    }
+
+    /// Returns whether a protected method `name` is defined by `self` or its
+    /// ancestors.
+    ///
+    /// This is equivalent to the `protected_method_defined?` method.
+    #[inline]
+    fn has_protected_method(self, name: impl Into<SymbolId>) -> bool {
+        _query_method(self.raw(), "protected_method_defined?", name.into())
+    }
+
     /// Evaluates `args` in the context of `self`.
     ///
     /// See the docs for `EvalArgs` for more info.
@@ -1,8 +1,8 @@
 //! Ruby modules.
 
-use std::fmt;
+use std::{fmt, os::raw::c_int};
 use crate::{
-    mixin::DefMixinError,
+    mixin::{DefMixinError, MethodFn},
     object::{NonNullObject, Ty},
     prelude::*,
     ruby,
@@ -159,6 +159,160 @@ impl Module {
         }
     }
 
+    /// Defines an instance method for `name` on `self` that calls `f` when
+    /// invoked.
+    ///
+    /// The method becomes available on any object that includes or is extended
+    /// by `self`. Because of this, its receiver is always an `AnyObject`.
+    ///
+    /// **Note:** This method can be unwieldy to use and so it is recommended to
+    /// instead call the convenience macro [`def_method!`], which also works
+    /// with modules.
+    ///
+    /// See [`Class::def_method`](struct.Class.html#method.def_method) for
+    /// more info on `MethodFn`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # rosy::vm::init().unwrap();
+    /// # rosy::protected(|| {
+    /// use rosy::prelude::*;
+    ///
+    /// let module = Module::def("Pair").unwrap();
+    ///
+    /// rosy::def_method!(module, "pair", |this| {
+    ///     Array::from_slice(&[this, this])
+    /// }).unwrap();
+    ///
+    /// let class = Class::def("Pairable").unwrap();
+    /// class.include(module);
+    ///
+    /// let object = class.new_instance().unwrap();
+    /// let pair = unsafe { object.call("pair") };
+    ///
+    /// assert_eq!(pair, Array::from_slice(&[object, object]));
+    /// # }).unwrap();
+    /// ```
+    ///
+    // Link to docs.rs since `Module` may either be in `module` module or root
+    /// [`def_method!`]: https://docs.rs/rosy/0.0.9/rosy/macro.def_method.html
+    #[inline]
+    pub fn def_method<N, F>(self, name: N, f: F) -> Result
+    where
+        N: Into<SymbolId>,
+        F: MethodFn<AnyObject>,
+    {
+        unsafe {
+            crate::mixin::_def_method(
+                self.raw(),
+                name.into().raw(),
+                f.raw_fn(),
+                F::ARITY,
+            )
+        }
+    }
+
+    /// Defines an instance method for `name` on `self` that calls `f` when
+    /// invoked.
+    ///
+    /// See [`def_method`](#method.def_method) for usage info.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `self` is not frozen or else a `FrozenError`
+    /// exception will be raised.
+    #[inline]
+    pub unsafe fn def_method_unchecked<N, F>(self, name: N, f: F)
+    where
+        N: Into<SymbolId>,
+        F: MethodFn<AnyObject>,
+    {
+        crate::mixin::_def_method_unchecked(
+            self.raw(),
+            name.into().raw(),
+            f.raw_fn(),
+            F::ARITY,
+        )
+    }
+
+    // monomorphization
+    unsafe fn _def_module_function(
+        self,
+        name: SymbolId,
+        f: unsafe extern "C" fn() -> ruby::VALUE,
+        arity: c_int,
+    ) -> Result {
+        crate::protected_no_panic(|| {
+            self._def_module_function_unchecked(name, f, arity)
+        })
+    }
+
+    #[inline]
+    unsafe fn _def_module_function_unchecked(
+        self,
+        name: SymbolId,
+        f: unsafe extern "C" fn() -> ruby::VALUE,
+        arity: c_int,
+    ) {
+        let name = name.name().as_ptr();
+        ruby::rb_define_module_function(self.raw(), name, Some(f), arity);
+    }
+
+    /// Defines a module function for `name` on `self` that calls `f` when
+    /// invoked.
+    ///
+    /// This is equivalent to calling `module_function` in Ruby: `f` becomes
+    /// both a singleton method on `self` and a private instance method
+    /// available to those that include `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # rosy::vm::init().unwrap();
+    /// use rosy::prelude::*;
+    ///
+    /// extern "C" fn twice(_this: AnyObject, obj: AnyObject) -> Array {
+    ///     Array::from_slice(&[obj, obj])
+    /// }
+    /// let twice: extern fn(_, _) -> _ = twice;
+    ///
+    /// let module = Module::def("Twice").unwrap();
+    /// module.def_module_function("twice", twice).unwrap();
+    ///
+    /// let value = Integer::from(3);
+    /// let array = unsafe { module.call_with("twice", &[value]) };
+    ///
+    /// assert_eq!(array, Array::from_slice(&[value, value]));
+    /// ```
+    #[inline]
+    pub fn def_module_function<N, F>(self, name: N, f: F) -> Result
+    where
+        N: Into<SymbolId>,
+        F: MethodFn<AnyObject>,
+    {
+        unsafe { self._def_module_function(name.into(), f.raw_fn(), F::ARITY) }
+    }
+
+    /// Defines a module function for `name` on `self` that calls `f` when
+    /// invoked.
+    ///
+    /// See [`def_module_function`](#method.def_module_function) for usage
+    /// info.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `self` is not frozen or else a `FrozenError`
+    /// exception will be raised.
+    #[inline]
+    pub unsafe fn def_module_function_unchecked<N, F>(self, name: N, f: F)
+    where
+        N: Into<SymbolId>,
+        F: MethodFn<AnyObject>,
+    {
+        self._def_module_function_unchecked(name.into(), f.raw_fn(), F::ARITY)
+    }
+
     /// Returns the name of `self` or `nil` if anonymous.
     #[inline]
     pub fn name(self) -> Option<String> {
@@ -0,0 +1,240 @@
+//! Ruby complex numbers.
+
+use std::{
+    fmt,
+    ops::{Add, Sub, Mul, Div, Neg},
+};
+use crate::{
+    exception::{TypeError, ZeroDivError},
+    prelude::*,
+    object::{NonNullObject, Ty},
+    ruby,
+};
+
+/// An instance of Ruby's `Complex` class.
+///
+/// The real and imaginary parts may each be an `Integer`, `Float` or
+/// [`Rational`](struct.Rational.html).
+///
+/// # Examples
+///
+/// ```
+/// # rosy::vm::init().unwrap();
+/// use rosy::{num::Complex, Integer};
+///
+/// let a = Complex::new(Integer::from(1), Integer::from(2)).unwrap();
+/// let b = Complex::new(Integer::from(3), Integer::from(-1)).unwrap();
+///
+/// let product = a * b;
+/// assert_eq!(product.to_string(), "5+5i");
+/// assert_eq!(product.to_f64_parts(), (5.0, 5.0));
+/// assert_eq!(product.real(), 5);
+/// ```
+#[derive(Clone, Copy, Debug)]
+#[repr(transparent)]
+pub struct Complex(NonNullObject);
+
+impl AsRef<AnyObject> for Complex {
+    #[inline]
+    fn as_ref(&self) -> &AnyObject { self.0.as_ref() }
+}
+
+impl From<Complex> for AnyObject {
+    #[inline]
+    fn from(obj: Complex) -> Self { obj.0.into() }
+}
+
+impl PartialEq for Complex {
+    #[inline]
+    fn eq(&self, other: &Complex) -> bool {
+        self.as_any_object() == other.as_any_object()
+    }
+}
+
+impl PartialEq<AnyObject> for Complex {
+    #[inline]
+    fn eq(&self, other: &AnyObject) -> bool {
+        self.as_any_object() == other
+    }
+}
+
+unsafe impl Object for Complex {
+    #[inline]
+    fn unique_id() -> Option<u128> {
+        Some(!(Ty::COMPLEX.id() as u128))
+    }
+
+    #[inline]
+    fn cast<A: Object>(object: A) -> Option<Self> {
+        if object.is_ty(Ty::COMPLEX) {
+            unsafe { Some(Self::cast_unchecked(object)) }
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn ty(self) -> Ty {
+        Ty::COMPLEX
+    }
+
+    #[inline]
+    fn is_ty(self, ty: Ty) -> bool {
+        self.ty() == ty
+    }
+}
+
+impl From<f64> for Complex {
+    #[inline]
+    fn from(real: f64) -> Self {
+        Complex::from_f64_parts(real, 0.0)
+    }
+}
+
+impl fmt::Display for Complex {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_any_object().fmt(f)
+    }
+}
+
+macro_rules! impl_ops {
+    ($($op:ident, $f:ident, $id:ident;)+) => { $(
+        impl $op for Complex {
+            type Output = Self;
+
+            #[inline]
+            fn $f(self, rhs: Complex) -> Self {
+                // Only `Complex#/` can raise, which is handled in `div`
+                unsafe {
+                    Self::cast_unchecked(self.call_with(SymbolId::$id(), &[rhs]))
+                }
+            }
+        }
+    )+ }
+}
+
+impl_ops! {
+    Add, add, plus_op;
+    Sub, sub, minus_op;
+    Mul, mul, mul_op;
+}
+
+impl Div for Complex {
+    type Output = Self;
+
+    /// Divides `self` by `rhs`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` is zero with exact parts. Use
+    /// [`checked_div`](struct.Complex.html#method.checked_div) to get an error
+    /// instead.
+    #[inline]
+    fn div(self, rhs: Complex) -> Self {
+        match self.checked_div(rhs) {
+            Ok(quotient) => quotient,
+            Err(_) => panic!("attempt to divide by zero"),
+        }
+    }
+}
+
+impl Neg for Complex {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        unsafe { Self::cast_unchecked(self.call(SymbolId::uminus_op())) }
+    }
+}
+
+impl Complex {
+    // Returns whether `obj` can be used as a part of a complex number
+    #[inline]
+    fn _is_real(obj: AnyObject) -> bool {
+        obj.is_integer() || obj.is_float() || obj.is_ty(Ty::RATIONAL)
+    }
+
+    /// Returns a new complex number for `real + imaginary * i`.
+    ///
+    /// # Errors
+    ///
+    /// A `TypeError` is returned if either part is not an `Integer`, `Float` or
+    /// `Rational`.
+    pub fn new(real: impl Object, imaginary: impl Object) -> Result<Self> {
+        let real = real.into_any_object();
+        let imaginary = imaginary.into_any_object();
+        for &part in &[real, imaginary] {
+            if !Self::_is_real(part) {
+                let message = format!(
+                    "expected a real number for part of a Complex, found {}",
+                    part.class().name(),
+                );
+                return Err(TypeError::new(message.as_str()).into_any_exception());
+            }
+        }
+        unsafe {
+            Ok(Self::from_raw(ruby::rb_complex_new(real.raw(), imaginary.raw())))
+        }
+    }
+
+    /// Returns a new complex number with `Float` parts.
+    #[inline]
+    pub fn from_f64_parts(real: f64, imaginary: f64) -> Self {
+        let real = Float::from(real);
+        let imaginary = Float::from(imaginary);
+        unsafe { Self::from_raw(ruby::rb_complex_new(real.raw(), imaginary.raw())) }
+    }
+
+    /// Returns the real part of `self`.
+    #[inline]
+    pub fn real(self) -> AnyObject {
+        unsafe { self.call(SymbolId::real()) }
+    }
+
+    /// Returns the imaginary part of `self`.
+    #[inline]
+    pub fn imaginary(self) -> AnyObject {
+        unsafe { self.call(SymbolId::imaginary()) }
+    }
+
+    /// Returns the real and imaginary parts of `self` as 64-bit floating point
+    /// numbers.
+    #[inline]
+    pub fn to_f64_parts(self) -> (f64, f64) {
+        unsafe {(
+            ruby::rb_num2dbl(self.real().raw()),
+            ruby::rb_num2dbl(self.imaginary().raw()),
+        )}
+    }
+
+    /// Returns `self / rhs`, or a `ZeroDivError` if `rhs` is zero with exact
+    /// parts.
+    ///
+    /// Division by zero with `Float` parts results in parts that are infinite
+    /// or NaN, as with `f64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # rosy::vm::init().unwrap();
+    /// use rosy::{num::Complex, Integer};
+    ///
+    /// let a = Complex::new(Integer::from(4), Integer::from(2)).unwrap();
+    /// let zero = Complex::new(Integer::zero(), Integer::zero()).unwrap();
+    ///
+    /// assert!(a.checked_div(zero).is_err());
+    /// ```
+    pub fn checked_div(self, rhs: Complex) -> Result<Self, ZeroDivError> {
+        let result = unsafe {
+            self.call_with_protected(SymbolId::div_op(), &[rhs])
+        };
+        match result {
+            Ok(quotient) => unsafe { Ok(Self::cast_unchecked(quotient)) },
+            Err(error) => match error.to_zero_div_error() {
+                Some(error) => Err(error),
+                None => panic!("unexpected exception: {}", error),
+            },
+        }
+    }
+}
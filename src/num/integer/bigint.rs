@@ -0,0 +1,84 @@
+//! Conversions between `Integer` and `num-bigint` types.
+
+use std::{
+    convert::TryFrom,
+    ptr,
+};
+use num_bigint::{BigInt, BigUint, Sign};
+use crate::{
+    prelude::*,
+    ruby,
+};
+use super::{
+    pack::{Options, Order},
+    TryFromIntegerError,
+};
+
+// `num-bigint` stores digits as `u32` with the least-significant first
+fn _options() -> Options {
+    Options::default().word_order(Order::Least)
+}
+
+// Returns the digits of the absolute value of `int`
+fn _abs_digits(int: Integer) -> Vec<u32> {
+    let len = unsafe { ruby::rb_absint_numwords(int.raw(), 32, ptr::null_mut()) };
+    let mut digits = vec![0u32; len];
+    // Unsigned words result in the absolute value being packed
+    int.pack_using(_options(), &mut digits);
+    digits
+}
+
+impl From<&BigInt> for Integer {
+    #[inline]
+    fn from(int: &BigInt) -> Self {
+        let (sign, digits) = int.to_u32_digits();
+        let options = match sign {
+            Sign::Minus => _options().is_negative(),
+            _ => _options(),
+        };
+        Integer::unpack_using(&digits, options)
+    }
+}
+
+impl From<BigInt> for Integer {
+    #[inline]
+    fn from(int: BigInt) -> Self {
+        Integer::from(&int)
+    }
+}
+
+impl From<&BigUint> for Integer {
+    #[inline]
+    fn from(int: &BigUint) -> Self {
+        Integer::unpack_using(&int.to_u32_digits(), _options())
+    }
+}
+
+impl From<BigUint> for Integer {
+    #[inline]
+    fn from(int: BigUint) -> Self {
+        Integer::from(&int)
+    }
+}
+
+impl From<Integer> for BigInt {
+    #[inline]
+    fn from(int: Integer) -> Self {
+        let sign = if int.is_negative() { Sign::Minus } else { Sign::Plus };
+        BigInt::from_slice(sign, &_abs_digits(int))
+    }
+}
+
+impl TryFrom<Integer> for BigUint {
+    type Error = TryFromIntegerError;
+
+    /// Converts `int` if it's not negative.
+    #[inline]
+    fn try_from(int: Integer) -> Result<Self, Self::Error> {
+        if int.is_negative() {
+            Err(TryFromIntegerError { target: "BigUint" })
+        } else {
+            Ok(BigUint::from_slice(&_abs_digits(int)))
+        }
+    }
+}
@@ -2,19 +2,26 @@
 
 use std::{
     cmp::Ordering,
+    convert::TryFrom,
+    error::Error,
     ffi::c_void,
     fmt,
     mem,
     ops,
     os::raw::c_int,
     slice,
+    str::FromStr,
 };
 use crate::{
+    exception::{RangeError, ZeroDivError},
     prelude::*,
     object::{NonNullObject, Ty},
     ruby,
 };
 
+#[cfg(feature = "num-bigint")]
+mod bigint;
+
 pub mod pack;
 use pack::Word;
 
@@ -45,6 +52,100 @@ use pack::Word;
 /// # }).unwrap();
 /// ```
 ///
+/// # Arithmetic Operations
+///
+/// Addition, subtraction, multiplication, division, remainder, negation and
+/// bit shifting are supported between integers of any size, as well as with
+/// Rust's integer primitives:
+///
+/// ```
+/// # rosy::vm::init().unwrap();
+/// # rosy::protected(|| {
+/// use rosy::Integer;
+///
+/// let big = Integer::from(u64::max_value());
+/// let sum = big + 1u8;
+///
+/// assert_eq!(sum, u128::from(u64::max_value()) + 1);
+/// assert_eq!(sum - big, 1);
+/// assert_eq!(big * 2u8, u128::from(u64::max_value()) * 2);
+/// assert_eq!(-sum, -(i128::from(u64::max_value()) + 1));
+/// assert_eq!(Integer::from(1) << 100u32, 1u128 << 100);
+/// # }).unwrap();
+/// ```
+///
+/// Shifting left panics if the result is too large for Ruby to create.
+///
+/// Like in Ruby, division rounds toward negative infinity and the remainder
+/// takes the sign of the divisor. Dividing by zero via `/` or `%` panics,
+/// whereas [`checked_div`](#method.checked_div) and
+/// [`checked_rem`](#method.checked_rem) return a `ZeroDivError`:
+///
+/// ```
+/// # rosy::vm::init().unwrap();
+/// # rosy::protected(|| {
+/// use rosy::Integer;
+///
+/// let a = Integer::from(-7);
+///
+/// assert_eq!(a / 2i32, -4);
+/// assert_eq!(a % 2i32, 1);
+/// assert!(a.checked_div(Integer::zero()).is_err());
+/// # }).unwrap();
+/// ```
+///
+/// Operations with a `Float` or `f64` result in a `Float`, where the remainder
+/// is also floored:
+///
+/// ```
+/// # rosy::vm::init().unwrap();
+/// use rosy::{Float, Integer};
+///
+/// let product: Float = Integer::from(3) * 0.5;
+/// assert_eq!(product, 1.5);
+///
+/// let rem: Float = Integer::from(-7) % 2.0;
+/// assert_eq!(rem, 1.0);
+/// assert_eq!(Float::from(7.5) % Integer::from(-2), -0.5);
+/// ```
+///
+/// # Parsing and Formatting
+///
+/// Integers of any size can be parsed from strings and formatted in binary,
+/// octal and hexadecimal:
+///
+/// ```
+/// # rosy::vm::init().unwrap();
+/// use rosy::Integer;
+///
+/// let int: Integer = "-340282366920938463463374607431768211456".parse().unwrap();
+///
+/// assert_eq!(format!("{:x}", int), "-100000000000000000000000000000000");
+/// assert_eq!(format!("{:#b}", Integer::from(5)), "0b101");
+/// assert_eq!(format!("{:#06X}", Integer::from(255)), "0x00FF");
+/// ```
+///
+/// # `num-bigint` Conversions
+///
+/// With the `num-bigint` feature enabled, integers of any size can be
+/// losslessly converted to and from `BigInt` and `BigUint`:
+///
+/// ```
+/// # rosy::vm::init().unwrap();
+/// # #[cfg(feature = "num-bigint")] {
+/// use std::convert::TryFrom;
+/// use num_bigint::{BigInt, BigUint};
+/// use rosy::Integer;
+///
+/// let big: BigInt = "-123456789012345678901234567890".parse().unwrap();
+/// let int = Integer::from(&big);
+///
+/// assert_eq!(int.to_string(), "-123456789012345678901234567890");
+/// assert_eq!(BigInt::from(int), big);
+/// assert!(BigUint::try_from(int).is_err());
+/// # }
+/// ```
+///
 /// [AND]: https://en.wikipedia.org/wiki/Logical_conjunction
 /// [OR]:  https://en.wikipedia.org/wiki/Logical_disjunction
 /// [XOR]: https://en.wikipedia.org/wiki/Exclusive_or
@@ -316,6 +417,131 @@ impl_bit_ops! {
     BitXor, bitxor, rb_big_xor;
 }
 
+macro_rules! forward_int_ops {
+    ($op:ident, $f:ident; $($t:ty)+) => { $(
+        impl ops::$op<$t> for Integer {
+            type Output = Integer;
+
+            #[inline]
+            fn $f(self, rhs: $t) -> Integer {
+                self.$f(Integer::from(rhs))
+            }
+        }
+
+        impl ops::$op<Integer> for $t {
+            type Output = Integer;
+
+            #[inline]
+            fn $f(self, rhs: Integer) -> Integer {
+                Integer::from(self).$f(rhs)
+            }
+        }
+    )+ }
+}
+
+// Ruby's floored modulo of floats, where the result takes the sign of `b`
+#[inline]
+fn _float_mod(a: f64, b: f64) -> f64 {
+    let rem = a % b;
+    if b * rem < 0.0 { rem + b } else { rem }
+}
+
+macro_rules! impl_arith_ops {
+    ($($op:ident, $f:ident, $imp:ident, $float:path;)+) => { $(
+        impl ops::$op for Integer {
+            type Output = Self;
+
+            #[inline]
+            fn $f(self, rhs: Self) -> Self {
+                self.$imp(rhs)
+            }
+        }
+
+        impl ops::$op<Float> for Integer {
+            type Output = Float;
+
+            #[inline]
+            fn $f(self, rhs: Float) -> Float {
+                $float(self.to_f64(), rhs.to_f64()).into()
+            }
+        }
+
+        impl ops::$op<Integer> for Float {
+            type Output = Float;
+
+            #[inline]
+            fn $f(self, rhs: Integer) -> Float {
+                $float(self.to_f64(), rhs.to_f64()).into()
+            }
+        }
+
+        impl ops::$op<f64> for Integer {
+            type Output = Float;
+
+            #[inline]
+            fn $f(self, rhs: f64) -> Float {
+                $float(self.to_f64(), rhs).into()
+            }
+        }
+
+        impl ops::$op<Integer> for f64 {
+            type Output = Float;
+
+            #[inline]
+            fn $f(self, rhs: Integer) -> Float {
+                $float(self, rhs.to_f64()).into()
+            }
+        }
+
+        forward_int_ops! {
+            $op, $f;
+            usize u128 u64 u32 u16 u8
+            isize i128 i64 i32 i16 i8
+        }
+    )+ }
+}
+
+impl_arith_ops! {
+    Add, add, _add, ops::Add::add;
+    Sub, sub, _sub, ops::Sub::sub;
+    Mul, mul, _mul, ops::Mul::mul;
+    Div, div, _div, ops::Div::div;
+    Rem, rem, _rem, _float_mod;
+}
+
+impl ops::Neg for Integer {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        Integer::zero() - self
+    }
+}
+
+macro_rules! impl_shift_ops {
+    ($($t:ty)+) => { $(
+        impl ops::Shl<$t> for Integer {
+            type Output = Self;
+
+            #[inline]
+            fn shl(self, rhs: $t) -> Self {
+                self._shl(rhs as usize)
+            }
+        }
+
+        impl ops::Shr<$t> for Integer {
+            type Output = Self;
+
+            #[inline]
+            fn shr(self, rhs: $t) -> Self {
+                self._shr(rhs as usize)
+            }
+        }
+    )+ }
+}
+
+impl_shift_ops! { usize u32 }
+
 impl fmt::Display for Integer {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -323,6 +549,101 @@ impl fmt::Display for Integer {
     }
 }
 
+macro_rules! impl_radix_fmt {
+    ($($fmt:ident, $radix:expr, $prefix:expr, $upper:expr;)+) => { $(
+        impl fmt::$fmt for Integer {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                let string = unsafe { self.to_s_radix_unchecked($radix) };
+                let string = unsafe { string.to_str_unchecked() };
+                let digits = string.trim_start_matches('-');
+                if $upper {
+                    let digits = digits.to_ascii_uppercase();
+                    f.pad_integral(self.is_positive(), $prefix, &digits)
+                } else {
+                    f.pad_integral(self.is_positive(), $prefix, digits)
+                }
+            }
+        }
+    )+ }
+}
+
+impl_radix_fmt! {
+    Binary,   2,  "0b", false;
+    Octal,    8,  "0o", false;
+    LowerHex, 16, "0x", false;
+    UpperHex, 16, "0x", true;
+}
+
+impl FromStr for Integer {
+    type Err = AnyException;
+
+    /// Parses a base 10 integer via
+    /// [`from_str_radix`](#method.from_str_radix).
+    #[inline]
+    fn from_str(s: &str) -> Result<Self> {
+        Self::from_str_radix(s, 10)
+    }
+}
+
+/// The error returned when converting an [`Integer`](struct.Integer.html) into
+/// a Rust integer primitive that can't represent its value.
+///
+/// This can be converted into a `RangeError` exception.
+///
+/// # Examples
+///
+/// ```
+/// # rosy::vm::init().unwrap();
+/// use std::convert::TryFrom;
+/// use rosy::Integer;
+///
+/// let int = Integer::from(300);
+///
+/// assert_eq!(u16::try_from(int), Ok(300));
+/// assert!(u8::try_from(int).is_err());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TryFromIntegerError {
+    pub(crate) target: &'static str,
+}
+
+impl fmt::Display for TryFromIntegerError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "integer too big to convert into `{}`", self.target)
+    }
+}
+
+impl Error for TryFromIntegerError {}
+
+impl From<TryFromIntegerError> for AnyException {
+    #[inline]
+    fn from(error: TryFromIntegerError) -> Self {
+        let message = error.to_string();
+        RangeError::new(message.as_str()).into_any_exception()
+    }
+}
+
+macro_rules! impl_try_from {
+    ($($t:ty)+) => { $(
+        impl TryFrom<Integer> for $t {
+            type Error = TryFromIntegerError;
+
+            #[inline]
+            fn try_from(int: Integer) -> std::result::Result<Self, Self::Error> {
+                int.to_value().ok_or(TryFromIntegerError {
+                    target: stringify!($t),
+                })
+            }
+        }
+    )+ }
+}
+
+impl_try_from! {
+    usize u128 u64 u32 u16 u8
+    isize i128 i64 i32 i16 i8
+}
+
 impl Integer {
     #[inline]
     const unsafe fn _from_raw(raw: ruby::VALUE) -> Self {
@@ -412,6 +733,234 @@ impl Integer {
         }
     }
 
+    // Returns `self` as a bignum, which `rb_big_*` functions require for their
+    // first argument
+    #[inline]
+    fn _to_big(self) -> ruby::VALUE {
+        match self.to_fixnum() {
+            Some(fixnum) => unsafe { ruby::rb_int2big(fixnum) },
+            None => self.raw(),
+        }
+    }
+
+    #[inline]
+    fn _add(self, rhs: Self) -> Self {
+        if let (Some(a), Some(b)) = (self.to_fixnum(), rhs.to_fixnum()) {
+            // Fixnums use one less bit than `isize`, so this can't overflow
+            return Self::from(a + b);
+        }
+        unsafe { Self::from_raw(ruby::rb_big_plus(self._to_big(), rhs.raw())) }
+    }
+
+    #[inline]
+    fn _sub(self, rhs: Self) -> Self {
+        if let (Some(a), Some(b)) = (self.to_fixnum(), rhs.to_fixnum()) {
+            return Self::from(a - b);
+        }
+        unsafe { Self::from_raw(ruby::rb_big_minus(self._to_big(), rhs.raw())) }
+    }
+
+    #[inline]
+    fn _mul(self, rhs: Self) -> Self {
+        if let (Some(a), Some(b)) = (self.to_fixnum(), rhs.to_fixnum()) {
+            return match a.checked_mul(b) {
+                Some(product) => Self::from(product),
+                None => Self::from(a as i128 * b as i128),
+            };
+        }
+        unsafe { Self::from_raw(ruby::rb_big_mul(self._to_big(), rhs.raw())) }
+    }
+
+    #[inline]
+    fn _div(self, rhs: Self) -> Self {
+        assert!(!rhs.is_zero(), "attempt to divide by zero");
+        unsafe { self._div_unchecked(rhs) }
+    }
+
+    #[inline]
+    fn _rem(self, rhs: Self) -> Self {
+        assert!(
+            !rhs.is_zero(),
+            "attempt to calculate the remainder with a divisor of zero",
+        );
+        unsafe { self._rem_unchecked(rhs) }
+    }
+
+    // Assumes `rhs` is non-zero, since Ruby raises otherwise
+    #[inline]
+    unsafe fn _div_unchecked(self, rhs: Self) -> Self {
+        if let (Some(a), Some(b)) = (self.to_fixnum(), rhs.to_fixnum()) {
+            // Round toward negative infinity like Ruby
+            let quotient = a / b;
+            let floored = a % b != 0 && (a < 0) != (b < 0);
+            return Self::from(quotient - floored as isize);
+        }
+        Self::from_raw(ruby::rb_big_div(self._to_big(), rhs.raw()))
+    }
+
+    // Assumes `rhs` is non-zero, since Ruby raises otherwise
+    #[inline]
+    unsafe fn _rem_unchecked(self, rhs: Self) -> Self {
+        if let (Some(a), Some(b)) = (self.to_fixnum(), rhs.to_fixnum()) {
+            // Take the sign of the divisor like Ruby
+            let rem = a % b;
+            let rem = if rem != 0 && (rem < 0) != (b < 0) { rem + b } else { rem };
+            return Self::from(rem);
+        }
+        Self::from_raw(ruby::rb_big_modulo(self._to_big(), rhs.raw()))
+    }
+
+    #[inline]
+    fn _shl(self, rhs: usize) -> Self {
+        if let Some(a) = self.to_fixnum() {
+            if rhs < mem::size_of::<isize>() * 8 {
+                let shifted = a << rhs;
+                if shifted >> rhs == a {
+                    return Self::from(shifted);
+                }
+            }
+        }
+        if self.is_zero() {
+            return self;
+        }
+        let rhs = Self::from(rhs);
+        // Ruby raises if the shift is too wide or the result can't be allocated
+        let result = crate::protected(|| unsafe {
+            Self::from_raw(ruby::rb_big_lshift(self._to_big(), rhs.raw()))
+        });
+        match result {
+            Ok(int) => int,
+            Err(error) => panic!("attempt to shift left with overflow: {}", error),
+        }
+    }
+
+    #[inline]
+    fn _shr(self, rhs: usize) -> Self {
+        if let Some(a) = self.to_fixnum() {
+            // An arithmetic shift rounds toward negative infinity like Ruby
+            let max = mem::size_of::<isize>() * 8 - 1;
+            return Self::from(a >> rhs.min(max));
+        }
+        let rhs = Self::from(rhs);
+        unsafe { Self::from_raw(ruby::rb_big_rshift(self.raw(), rhs.raw())) }
+    }
+
+    /// Returns `self / rhs`, or a `ZeroDivError` if `rhs` is zero.
+    ///
+    /// Like in Ruby, the result is rounded toward negative infinity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # rosy::vm::init().unwrap();
+    /// use rosy::Integer;
+    ///
+    /// let a = Integer::from(-9);
+    ///
+    /// assert_eq!(a.checked_div(Integer::from(4)).unwrap(), -3);
+    /// assert!(a.checked_div(Integer::zero()).is_err());
+    /// ```
+    #[inline]
+    pub fn checked_div(self, rhs: Integer) -> Result<Self, ZeroDivError> {
+        if rhs.is_zero() {
+            Err(ZeroDivError::new("divided by 0"))
+        } else {
+            unsafe { Ok(self._div_unchecked(rhs)) }
+        }
+    }
+
+    /// Returns `self % rhs`, or a `ZeroDivError` if `rhs` is zero.
+    ///
+    /// Like in Ruby, the result takes the sign of `rhs`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # rosy::vm::init().unwrap();
+    /// use rosy::Integer;
+    ///
+    /// let a = Integer::from(-9);
+    ///
+    /// assert_eq!(a.checked_rem(Integer::from(4)).unwrap(), 3);
+    /// assert!(a.checked_rem(Integer::zero()).is_err());
+    /// ```
+    #[inline]
+    pub fn checked_rem(self, rhs: Integer) -> Result<Self, ZeroDivError> {
+        if rhs.is_zero() {
+            Err(ZeroDivError::new("divided by 0"))
+        } else {
+            unsafe { Ok(self._rem_unchecked(rhs)) }
+        }
+    }
+
+    /// Returns `self` raised to the power of `exp`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result is too large for Ruby to represent as an
+    /// `Integer`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # rosy::vm::init().unwrap();
+    /// use rosy::Integer;
+    ///
+    /// let two = Integer::from(2);
+    ///
+    /// assert_eq!(two.pow(10), 1024);
+    /// assert_eq!(two.pow(100), 2u128.pow(100));
+    /// ```
+    #[inline]
+    pub fn pow(self, exp: u32) -> Self {
+        if let Some(result) = self.to_fixnum().and_then(|a| a.checked_pow(exp)) {
+            return Self::from(result);
+        }
+        let exp = Self::from(exp);
+        let result = unsafe {
+            AnyObject::from_raw(ruby::rb_big_pow(self._to_big(), exp.raw()))
+        };
+        // Ruby returns `Infinity` if the result is too large
+        result.to_integer().expect("`Integer::pow` result is too large")
+    }
+
+    /// Parses an integer of any size from `s` in the given base.
+    ///
+    /// Parsing follows the rules of Ruby's `Integer()` method: leading and
+    /// trailing whitespace is ignored, digits may be separated by single
+    /// underscores and a prefix like `0x` is allowed if it matches `radix`. A
+    /// `radix` of 0 determines the base from the prefix, defaulting to 10.
+    ///
+    /// # Errors
+    ///
+    /// An `ArgumentError` is returned if `s` is not a valid integer or if
+    /// `radix` is not 0 or within `2..=36`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # rosy::vm::init().unwrap();
+    /// use rosy::Integer;
+    ///
+    /// let int = Integer::from_str_radix("ffff_ffff_ffff_ffff_ffff", 16).unwrap();
+    /// assert_eq!(int, 0xffff_ffff_ffff_ffff_ffffu128);
+    ///
+    /// let int = Integer::from_str_radix("0b101", 0).unwrap();
+    /// assert_eq!(int, 5);
+    ///
+    /// assert!(Integer::from_str_radix("12z", 10).is_err());
+    /// ```
+    pub fn from_str_radix(s: &str, radix: u32) -> Result<Self> {
+        // Ruby treats negative bases specially, so prevent wrapping into one
+        let radix = if radix > 36 { 37 } else { radix as c_int };
+        let string = String::from(s);
+        unsafe {
+            crate::protected_no_panic(|| Self::from_raw(
+                ruby::rb_str_to_inum(string.raw(), radix, 1)
+            ))
+        }
+    }
+
     /// Returns whether `self == 0`.
     #[inline]
     pub fn is_zero(self) -> bool {
@@ -723,6 +1272,14 @@ mod tests {
         }).unwrap();
     }
 
+    #[test]
+    #[should_panic(expected = "attempt to shift left with overflow")]
+    fn shl_overflow() {
+        crate::vm::init().unwrap();
+
+        let _ = Integer::from(1) << !0usize;
+    }
+
     #[test]
     fn bit_ops() {
         crate::vm::init().unwrap();
@@ -759,4 +1316,68 @@ mod tests {
             }
         }).unwrap();
     }
+
+    #[test]
+    fn arith_ops() {
+        crate::vm::init().unwrap();
+
+        let values: &[i128] = &[
+            0,
+            1,
+            -1,
+            7,
+            -7,
+            Integer::max_fixnum().to_fixnum_unchecked() as i128,
+            Integer::min_fixnum().to_fixnum_unchecked() as i128,
+            (1 << 63) - 1,
+            -(1 << 63),
+            (1 << 64) - 1,
+        ];
+
+        // Ruby's floored division and modulo
+        fn div_floor(a: i128, b: i128) -> i128 {
+            let q = a / b;
+            if a % b != 0 && (a < 0) != (b < 0) { q - 1 } else { q }
+        }
+
+        fn rem_floor(a: i128, b: i128) -> i128 {
+            let r = a % b;
+            if r != 0 && (r < 0) != (b < 0) { r + b } else { r }
+        }
+
+        crate::protected(|| {
+            for &a in values {
+                let a_int = Integer::from(a);
+                assert_eq!(-a_int, -a);
+                assert_eq!(a_int << 3u32, a << 3);
+                assert_eq!(a_int >> 3u32, a >> 3);
+
+                for &b in values {
+                    let b_int = Integer::from(b);
+
+                    assert_eq!(a_int + b_int, a + b);
+                    assert_eq!(a_int - b_int, a - b);
+
+                    if b == 0 {
+                        assert!(a_int.checked_div(b_int).is_err());
+                        assert!(a_int.checked_rem(b_int).is_err());
+                    } else {
+                        assert_eq!(a_int / b_int, div_floor(a, b));
+                        assert_eq!(a_int % b_int, rem_floor(a, b));
+
+                        let b_float = Float::from(b_int.to_f64());
+                        let rem = unsafe {
+                            Float::from(a_int.to_f64()).call_with("%", &[b_float])
+                        };
+                        assert_eq!(a_int % b_float, rem);
+                    }
+                }
+            }
+
+            let max = (1i128 << 63) - 1;
+            let max_int = Integer::from(max);
+            assert_eq!(max_int * max_int, max * max);
+            assert_eq!(max_int.pow(2), max.pow(2));
+        }).unwrap();
+    }
 }
@@ -6,12 +6,18 @@ use crate::{
     ruby,
 };
 
+mod complex;
 mod float;
 mod integer;
+mod numeric;
+mod rational;
 
 pub use self::{
+    complex::*,
     float::*,
     integer::*,
+    numeric::*,
+    rational::*,
 };
 
 impl PartialEq<Integer> for Float {
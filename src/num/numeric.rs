@@ -0,0 +1,408 @@
+//! Ruby numbers of any type.
+
+use std::{
+    fmt,
+    ops::{Add, Sub, Mul, Div, Rem},
+};
+use crate::{
+    object::{CastError, NonNullObject, Ty},
+    prelude::*,
+    ruby,
+    util::Sealed,
+};
+use super::{Complex, Rational};
+
+// Calls `method` on `obj`, panicking if it raises
+fn _call(obj: AnyObject, method: SymbolId) -> AnyObject {
+    match unsafe { obj.call_protected(method) } {
+        Ok(obj) => obj,
+        Err(error) => panic!("{}", error),
+    }
+}
+
+// Calls `method` on `obj`, returning whether the result is truthy
+#[inline]
+fn _call_bool(obj: AnyObject, method: SymbolId) -> bool {
+    crate::util::test_value(_call(obj, method).raw())
+}
+
+// Calls the binary operator `op`, which is where Ruby performs coercion
+fn _binary_op(lhs: AnyObject, op: SymbolId, rhs: AnyObject) -> Result<AnyNumeric> {
+    let result = unsafe { lhs.call_with_protected(op, &[rhs])? };
+    AnyNumeric::cast(result).ok_or_else(|| {
+        CastError::_new::<AnyNumeric>(result).into()
+    })
+}
+
+/// A type whose instances inherit from Ruby's `Numeric` class.
+///
+/// This is implemented for [`Integer`], [`Float`], [`Rational`] and
+/// [`AnyNumeric`], which allows for taking any number from Ruby without
+/// checking each type in turn.
+///
+/// Arithmetic goes through Ruby's operators, which use the `coerce` protocol
+/// to bring both sides to a common type. Adding a `Float` to an `Integer`, for
+/// example, results in a `Float`.
+///
+/// # Examples
+///
+/// ```
+/// # rosy::vm::init().unwrap();
+/// use rosy::{num::{AnyNumeric, Numeric, Rational}, prelude::*};
+///
+/// fn sum(values: &[AnyNumeric]) -> Result<AnyNumeric> {
+///     let mut total = Integer::from(0).into_any_numeric();
+///     for &value in values {
+///         total = total.try_add(value)?;
+///     }
+///     Ok(total)
+/// }
+///
+/// let values = [
+///     Integer::from(1).into(),
+///     Float::from(0.5).into(),
+///     Rational::new(1, 4).unwrap().into(),
+/// ];
+/// let total = sum(&values).unwrap();
+///
+/// assert_eq!(total.to_f64(), 1.75);
+/// assert!(!total.is_integer());
+/// assert!(Integer::from(1).try_div(Integer::from(0)).is_err());
+/// ```
+///
+/// [`Integer`]: ../struct.Integer.html
+/// [`Float`]: ../struct.Float.html
+/// [`Rational`]: struct.Rational.html
+/// [`AnyNumeric`]: struct.AnyNumeric.html
+pub trait Numeric: Object + Sealed {
+    /// Returns `self` as an `AnyNumeric`.
+    #[inline]
+    fn into_any_numeric(self) -> AnyNumeric {
+        unsafe { AnyNumeric::cast_unchecked(self) }
+    }
+
+    /// Returns the nearest 64-bit floating point number to `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` can't be converted to a `Float`, such as a `Complex`
+    /// with a non-zero imaginary part.
+    #[inline]
+    fn to_f64(self) -> f64 {
+        match crate::protected(|| unsafe { ruby::rb_num2dbl(self.raw()) }) {
+            Ok(f) => f,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    /// Returns whether `self` is an `Integer`.
+    ///
+    /// This is equivalent to the `integer?` method.
+    #[inline]
+    fn is_integer(self) -> bool {
+        _call_bool(self.into_any_object(), SymbolId::integer_q())
+    }
+
+    /// Returns whether `self` is neither infinite nor NaN.
+    ///
+    /// This is equivalent to the `finite?` method.
+    #[inline]
+    fn is_finite(self) -> bool {
+        _call_bool(self.into_any_object(), SymbolId::finite_q())
+    }
+
+    /// Returns whether `self` is zero.
+    ///
+    /// This is equivalent to the `zero?` method.
+    #[inline]
+    fn is_zero(self) -> bool {
+        _call_bool(self.into_any_object(), SymbolId::zero_q())
+    }
+
+    /// Returns the absolute value of `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `abs` method raises an exception or doesn't return an
+    /// instance of `Self`.
+    #[inline]
+    fn abs(self) -> Self {
+        let obj = _call(self.into_any_object(), SymbolId::abs());
+        match Self::cast(obj) {
+            Some(abs) => abs,
+            None => panic!("{}", CastError::_new::<Self>(obj)),
+        }
+    }
+
+    /// Returns `other` and `self` converted to a common type, in that order.
+    ///
+    /// This is equivalent to the `coerce` method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # rosy::vm::init().unwrap();
+    /// use rosy::{num::Numeric, prelude::*};
+    ///
+    /// let (a, b) = Integer::from(1).coerce(Float::from(2.5)).unwrap();
+    ///
+    /// assert_eq!(a, Float::from(2.5));
+    /// assert_eq!(b, Float::from(1.0));
+    /// ```
+    fn coerce(self, other: impl Numeric) -> Result<(AnyNumeric, AnyNumeric)> {
+        let pair = unsafe {
+            self.call_with_protected(SymbolId::coerce(), &[other])?
+        };
+        let numeric = |obj: Option<AnyObject>| -> Result<AnyNumeric> {
+            let obj = obj.unwrap_or(AnyObject::nil());
+            AnyNumeric::cast(obj).ok_or_else(|| {
+                CastError::_new::<AnyNumeric>(obj).into()
+            })
+        };
+        match pair.to_array() {
+            Some(pair) if pair.len() == 2 => {
+                Ok((numeric(pair.get(0))?, numeric(pair.get(1))?))
+            },
+            _ => Err(CastError::_new::<Array>(pair).into()),
+        }
+    }
+
+    /// Returns `self + rhs`, or the exception raised by Ruby.
+    #[inline]
+    fn try_add(self, rhs: impl Numeric) -> Result<AnyNumeric> {
+        let op = SymbolId::plus_op();
+        _binary_op(self.into_any_object(), op, rhs.into_any_object())
+    }
+
+    /// Returns `self - rhs`, or the exception raised by Ruby.
+    #[inline]
+    fn try_sub(self, rhs: impl Numeric) -> Result<AnyNumeric> {
+        let op = SymbolId::minus_op();
+        _binary_op(self.into_any_object(), op, rhs.into_any_object())
+    }
+
+    /// Returns `self * rhs`, or the exception raised by Ruby.
+    #[inline]
+    fn try_mul(self, rhs: impl Numeric) -> Result<AnyNumeric> {
+        let op = SymbolId::mul_op();
+        _binary_op(self.into_any_object(), op, rhs.into_any_object())
+    }
+
+    /// Returns `self / rhs`, or the exception raised by Ruby, such as a
+    /// `ZeroDivisionError`.
+    #[inline]
+    fn try_div(self, rhs: impl Numeric) -> Result<AnyNumeric> {
+        let op = SymbolId::div_op();
+        _binary_op(self.into_any_object(), op, rhs.into_any_object())
+    }
+
+    /// Returns `self % rhs`, or the exception raised by Ruby, such as a
+    /// `ZeroDivisionError`.
+    #[inline]
+    fn try_rem(self, rhs: impl Numeric) -> Result<AnyNumeric> {
+        let op = SymbolId::rem_op();
+        _binary_op(self.into_any_object(), op, rhs.into_any_object())
+    }
+}
+
+impl Sealed for Integer {}
+
+impl Numeric for Integer {
+    #[inline]
+    fn to_f64(self) -> f64 {
+        Integer::to_f64(self)
+    }
+
+    #[inline]
+    fn is_integer(self) -> bool {
+        true
+    }
+
+    #[inline]
+    fn is_finite(self) -> bool {
+        true
+    }
+
+    #[inline]
+    fn is_zero(self) -> bool {
+        Integer::is_zero(self)
+    }
+
+    #[inline]
+    fn abs(self) -> Self {
+        if self.is_negative() { -self } else { self }
+    }
+}
+
+impl Sealed for Float {}
+
+impl Numeric for Float {
+    #[inline]
+    fn to_f64(self) -> f64 {
+        Float::to_f64(self)
+    }
+
+    #[inline]
+    fn is_integer(self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn is_finite(self) -> bool {
+        Float::to_f64(self).is_finite()
+    }
+
+    #[inline]
+    fn is_zero(self) -> bool {
+        Float::to_f64(self) == 0.0
+    }
+
+    #[inline]
+    fn abs(self) -> Self {
+        Float::from(Float::to_f64(self).abs())
+    }
+}
+
+impl Sealed for Rational {}
+
+impl Numeric for Rational {
+    #[inline]
+    fn to_f64(self) -> f64 {
+        Rational::to_f64(self)
+    }
+
+    #[inline]
+    fn is_integer(self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn is_finite(self) -> bool {
+        true
+    }
+
+    #[inline]
+    fn is_zero(self) -> bool {
+        Rational::is_zero(self)
+    }
+
+    #[inline]
+    fn abs(self) -> Self {
+        if self.numerator().is_negative() { -self } else { self }
+    }
+}
+
+/// An instance of Ruby's `Numeric` class or one of its subclasses.
+///
+/// This includes built-in types like `Integer`, `Float`, `Rational` and
+/// `Complex`, as well as user-defined numbers. Its methods are provided by the
+/// [`Numeric`](trait.Numeric.html) trait.
+///
+/// The arithmetic operators panic if Ruby raises an exception. Use the `try_`
+/// methods of [`Numeric`](trait.Numeric.html) to get the exception instead.
+///
+/// # Examples
+///
+/// ```
+/// # rosy::vm::init().unwrap();
+/// use rosy::{num::{AnyNumeric, Numeric}, prelude::*};
+///
+/// let a = AnyNumeric::from(Integer::from(3));
+/// let b = AnyNumeric::from(Float::from(-1.5));
+///
+/// assert_eq!((a * b).to_f64(), -4.5);
+/// assert_eq!(b.abs().to_f64(), 1.5);
+/// assert!(AnyNumeric::cast(String::from("3")).is_none());
+/// ```
+#[derive(Clone, Copy, Debug)]
+#[repr(transparent)]
+pub struct AnyNumeric(NonNullObject);
+
+impl AsRef<AnyObject> for AnyNumeric {
+    #[inline]
+    fn as_ref(&self) -> &AnyObject { self.0.as_ref() }
+}
+
+impl From<AnyNumeric> for AnyObject {
+    #[inline]
+    fn from(obj: AnyNumeric) -> Self { obj.0.into() }
+}
+
+impl<O: Object> PartialEq<O> for AnyNumeric {
+    #[inline]
+    fn eq(&self, other: &O) -> bool {
+        self.as_any_object() == other
+    }
+}
+
+unsafe impl Object for AnyNumeric {
+    #[inline]
+    fn cast<A: Object>(obj: A) -> Option<Self> {
+        let is_numeric = match obj.ty() {
+            Ty::FIXNUM   |
+            Ty::BIGNUM   |
+            Ty::FLOAT    |
+            Ty::RATIONAL |
+            Ty::COMPLEX  => true,
+            _ => obj.class().inherits(Class::numeric()),
+        };
+        if is_numeric {
+            unsafe { Some(Self::cast_unchecked(obj)) }
+        } else {
+            None
+        }
+    }
+}
+
+impl Sealed for AnyNumeric {}
+
+impl Numeric for AnyNumeric {
+    #[inline]
+    fn into_any_numeric(self) -> AnyNumeric {
+        self
+    }
+}
+
+macro_rules! impl_from {
+    ($($t:ty)+) => { $(
+        impl From<$t> for AnyNumeric {
+            #[inline]
+            fn from(num: $t) -> Self {
+                unsafe { Self::cast_unchecked(num) }
+            }
+        }
+    )+ };
+}
+
+impl_from! { Integer Float Rational Complex }
+
+impl fmt::Display for AnyNumeric {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_any_object().fmt(f)
+    }
+}
+
+macro_rules! impl_ops {
+    ($($op:ident, $f:ident, $try_f:ident;)+) => { $(
+        impl $op for AnyNumeric {
+            type Output = Self;
+
+            #[inline]
+            fn $f(self, rhs: AnyNumeric) -> Self {
+                match self.$try_f(rhs) {
+                    Ok(result) => result,
+                    Err(error) => panic!("{}", error),
+                }
+            }
+        }
+    )+ };
+}
+
+impl_ops! {
+    Add, add, try_add;
+    Sub, sub, try_sub;
+    Mul, mul, try_mul;
+    Div, div, try_div;
+    Rem, rem, try_rem;
+}
@@ -0,0 +1,297 @@
+//! Ruby rational numbers.
+
+use std::{
+    fmt,
+    ops::{Add, Sub, Mul, Div, Neg},
+};
+use crate::{
+    exception::ZeroDivError,
+    prelude::*,
+    object::{NonNullObject, Ty},
+    ruby,
+};
+
+/// An instance of Ruby's `Rational` class.
+///
+/// Rationals are exact fractions of integers of any size, which makes them
+/// suitable for values that must not lose precision, such as money.
+///
+/// # Examples
+///
+/// ```
+/// # rosy::vm::init().unwrap();
+/// use rosy::{num::Rational, Integer};
+///
+/// let price = Rational::new(1999, 100).unwrap();
+/// let total = price * Integer::from(3);
+///
+/// assert_eq!(total.to_string(), "5997/100");
+/// assert_eq!(total.to_pair(), (Integer::from(5997), Integer::from(100)));
+/// assert_eq!(total.to_f64(), 59.97);
+/// ```
+///
+/// Like in Ruby, values are always stored in lowest terms with a positive
+/// denominator:
+///
+/// ```
+/// # rosy::vm::init().unwrap();
+/// use rosy::num::Rational;
+///
+/// let half = Rational::new(-3, -6).unwrap();
+///
+/// assert_eq!(half.numerator(), 1);
+/// assert_eq!(half.denominator(), 2);
+/// assert_eq!(half, Rational::new(1, 2).unwrap());
+/// ```
+#[derive(Clone, Copy, Debug)]
+#[repr(transparent)]
+pub struct Rational(NonNullObject);
+
+impl AsRef<AnyObject> for Rational {
+    #[inline]
+    fn as_ref(&self) -> &AnyObject { self.0.as_ref() }
+}
+
+impl From<Rational> for AnyObject {
+    #[inline]
+    fn from(obj: Rational) -> Self { obj.0.into() }
+}
+
+impl PartialEq for Rational {
+    #[inline]
+    fn eq(&self, other: &Rational) -> bool {
+        // Both are in lowest terms, so this compares exactly
+        self.to_pair() == other.to_pair()
+    }
+}
+
+impl Eq for Rational {}
+
+impl PartialEq<AnyObject> for Rational {
+    #[inline]
+    fn eq(&self, other: &AnyObject) -> bool {
+        self.as_any_object() == other
+    }
+}
+
+unsafe impl Object for Rational {
+    #[inline]
+    fn unique_id() -> Option<u128> {
+        Some(!(Ty::RATIONAL.id() as u128))
+    }
+
+    #[inline]
+    fn cast<A: Object>(object: A) -> Option<Self> {
+        if object.is_ty(Ty::RATIONAL) {
+            unsafe { Some(Self::cast_unchecked(object)) }
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn ty(self) -> Ty {
+        Ty::RATIONAL
+    }
+
+    #[inline]
+    fn is_ty(self, ty: Ty) -> bool {
+        self.ty() == ty
+    }
+}
+
+impl From<Integer> for Rational {
+    #[inline]
+    fn from(int: Integer) -> Self {
+        unsafe { Self::_new_unchecked(int, Integer::from(1)) }
+    }
+}
+
+impl From<Rational> for (Integer, Integer) {
+    #[inline]
+    fn from(rational: Rational) -> Self {
+        rational.to_pair()
+    }
+}
+
+impl fmt::Display for Rational {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_any_object().fmt(f)
+    }
+}
+
+impl Add for Rational {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Rational) -> Self {
+        let (a, b) = self.to_pair();
+        let (c, d) = rhs.to_pair();
+        unsafe { Self::_new_unchecked(a * d + c * b, b * d) }
+    }
+}
+
+impl Sub for Rational {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Rational) -> Self {
+        self + -rhs
+    }
+}
+
+impl Mul for Rational {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Rational) -> Self {
+        let (a, b) = self.to_pair();
+        let (c, d) = rhs.to_pair();
+        unsafe { Self::_new_unchecked(a * c, b * d) }
+    }
+}
+
+impl Div for Rational {
+    type Output = Self;
+
+    /// Divides `self` by `rhs`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` is zero. Use
+    /// [`checked_div`](struct.Rational.html#method.checked_div) to get an
+    /// error instead.
+    #[inline]
+    fn div(self, rhs: Rational) -> Self {
+        assert!(!rhs.is_zero(), "attempt to divide by zero");
+        self * rhs._recip()
+    }
+}
+
+impl Neg for Rational {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        let (a, b) = self.to_pair();
+        unsafe { Self::_new_unchecked(-a, b) }
+    }
+}
+
+macro_rules! forward_int_ops {
+    ($($op:ident, $f:ident;)+) => { $(
+        impl $op<Integer> for Rational {
+            type Output = Self;
+
+            #[inline]
+            fn $f(self, rhs: Integer) -> Self {
+                self.$f(Rational::from(rhs))
+            }
+        }
+
+        impl $op<Rational> for Integer {
+            type Output = Rational;
+
+            #[inline]
+            fn $f(self, rhs: Rational) -> Rational {
+                Rational::from(self).$f(rhs)
+            }
+        }
+    )+ }
+}
+
+forward_int_ops! {
+    Add, add;
+    Sub, sub;
+    Mul, mul;
+    Div, div;
+}
+
+impl Rational {
+    // Assumes `denominator` is non-zero, since Ruby raises otherwise
+    #[inline]
+    unsafe fn _new_unchecked(numerator: Integer, denominator: Integer) -> Self {
+        Self::from_raw(ruby::rb_rational_new(numerator.raw(), denominator.raw()))
+    }
+
+    // Assumes `self` is non-zero
+    #[inline]
+    fn _recip(self) -> Self {
+        let (a, b) = self.to_pair();
+        unsafe { Self::_new_unchecked(b, a) }
+    }
+
+    /// Returns a new rational for `numerator / denominator`, reduced to its
+    /// lowest terms.
+    ///
+    /// # Errors
+    ///
+    /// A `ZeroDivError` is returned if `denominator` is zero.
+    #[inline]
+    pub fn new(
+        numerator: impl Into<Integer>,
+        denominator: impl Into<Integer>,
+    ) -> Result<Self, ZeroDivError> {
+        let denominator = denominator.into();
+        if denominator.is_zero() {
+            Err(ZeroDivError::new("divided by 0"))
+        } else {
+            unsafe { Ok(Self::_new_unchecked(numerator.into(), denominator)) }
+        }
+    }
+
+    /// Returns the numerator of `self`, which has the same sign as `self`.
+    #[inline]
+    pub fn numerator(self) -> Integer {
+        unsafe { Integer::from_raw(ruby::rb_rational_num(self.raw())) }
+    }
+
+    /// Returns the denominator of `self`, which is always positive.
+    #[inline]
+    pub fn denominator(self) -> Integer {
+        unsafe { Integer::from_raw(ruby::rb_rational_den(self.raw())) }
+    }
+
+    /// Returns the numerator and denominator of `self`.
+    #[inline]
+    pub fn to_pair(self) -> (Integer, Integer) {
+        (self.numerator(), self.denominator())
+    }
+
+    /// Returns whether `self == 0`.
+    #[inline]
+    pub fn is_zero(self) -> bool {
+        self.numerator().is_zero()
+    }
+
+    /// Returns the nearest 64-bit floating point number to `self`.
+    #[inline]
+    pub fn to_f64(self) -> f64 {
+        unsafe { ruby::rb_num2dbl(self.raw()) }
+    }
+
+    /// Returns `self / rhs`, or a `ZeroDivError` if `rhs` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # rosy::vm::init().unwrap();
+    /// use rosy::num::Rational;
+    ///
+    /// let a = Rational::new(1, 2).unwrap();
+    /// let b = Rational::new(3, 4).unwrap();
+    /// let zero = Rational::new(0, 1).unwrap();
+    ///
+    /// assert_eq!(a.checked_div(b).unwrap(), Rational::new(2, 3).unwrap());
+    /// assert!(a.checked_div(zero).is_err());
+    /// ```
+    #[inline]
+    pub fn checked_div(self, rhs: Rational) -> Result<Self, ZeroDivError> {
+        if rhs.is_zero() {
+            Err(ZeroDivError::new("divided by 0"))
+        } else {
+            Ok(self * rhs._recip())
+        }
+    }
+}
@@ -4,7 +4,7 @@ use std::{
     marker::PhantomData,
 };
 use crate::{
-    object::Ty,
+    object::{Kind, Ty},
     prelude::*,
     ruby,
 };
@@ -207,6 +207,104 @@ impl AnyObject {
         AnyObject::from_raw(ruby::rb_call_super(len as _, ptr))
     }
 
+    /// Returns whether a block was given to the current method.
+    ///
+    /// # Examples
+    ///
+    /// A Rust-defined method can take a block and yield to it:
+    ///
+    /// ```
+    /// # rosy::vm::init().unwrap();
+    /// # rosy::protected(|| {
+    /// use std::ffi::CStr;
+    /// use rosy::prelude::*;
+    ///
+    /// let class = Class::object();
+    ///
+    /// rosy::def_method!(class, "yield_self_twice", |this| unsafe {
+    ///     if AnyObject::block_given() {
+    ///         AnyObject::yield_with_unchecked(&[this]);
+    ///         AnyObject::yield_with_unchecked(&[this])
+    ///     } else {
+    ///         AnyObject::nil()
+    ///     }
+    /// }).unwrap();
+    ///
+    /// let script = b"n = 0; 5.yield_self_twice { |x| n += x }; n\0";
+    /// let script = CStr::from_bytes_with_nul(script).unwrap();
+    /// let result = unsafe { rosy::vm::eval(script) };
+    ///
+    /// assert_eq!(result, Integer::from(10));
+    /// # }).unwrap();
+    /// ```
+    ///
+    /// The block can also be captured as a [`Proc`](proc/struct.Proc.html) via
+    /// [`Proc::from_block`](proc/struct.Proc.html#method.from_block).
+    #[inline]
+    pub fn block_given() -> bool {
+        unsafe { ruby::rb_block_given_p() != 0 }
+    }
+
+    /// Yields `args` to the block given to the current method in the context
+    /// of a method.
+    ///
+    /// An exception raised by the block is returned as an error, whereas a
+    /// `break` or `throw` out of the block continues past the current method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # rosy::vm::init().unwrap();
+    /// # rosy::protected(|| {
+    /// use std::ffi::CStr;
+    /// use rosy::prelude::*;
+    ///
+    /// rosy::def_method!(Class::object(), "yield_twice", |_this| -> Result<AnyObject> {
+    ///     AnyObject::yield_with(&[Integer::from(1)])?;
+    ///     AnyObject::yield_with(&[Integer::from(2)])
+    /// }).unwrap();
+    ///
+    /// let script = b"n = 0; [yield_twice { |i| n += i; break :done }, n]\0";
+    /// let script = CStr::from_bytes_with_nul(script).unwrap();
+    ///
+    /// let result = unsafe { rosy::vm::eval(script) };
+    /// let expected = [AnyObject::from(Symbol::from("done")), AnyObject::from(1)];
+    /// assert_eq!(result, &expected[..]);
+    /// # }).unwrap();
+    /// ```
+    #[inline]
+    pub fn yield_with(args: &[impl Object]) -> Result<AnyObject> {
+        Self::_yield_with(Self::convert_slice(args))
+    }
+
+    // monomorphization
+    fn _yield_with(args: &[AnyObject]) -> Result<AnyObject> {
+        let result = crate::protected::_catch_jump(|| unsafe {
+            Self::yield_with_unchecked(args)
+        });
+        match result {
+            Ok(value) => Ok(value),
+            Err(ref jump) if jump.is_raise() => unsafe {
+                Err(AnyException::_take_current())
+            },
+            Err(jump) => unsafe { jump.resume() },
+        }
+    }
+
+    /// Yields `args` to the block given to the current method in the context
+    /// of a method, without checking for an exception.
+    ///
+    /// # Safety
+    ///
+    /// A `LocalJumpError` exception will be raised if no block was given. The
+    /// block itself may also raise an exception.
+    #[inline]
+    pub unsafe fn yield_with_unchecked(args: &[impl Object]) -> AnyObject {
+        let len = args.len();
+        let ptr = args.as_ptr() as *const ruby::VALUE;
+        AnyObject::from_raw(ruby::rb_yield_values2(len as _, ptr))
+    }
+
     /// An alternative to
     /// [`Object::from_raw`](trait.Object.html#method.from_raw) that works in a
     /// `const` context.
@@ -417,4 +515,53 @@ impl AnyObject {
             None
         }
     }
+    /// Returns the kind of `self`, with `self` cast to the corresponding type.
+    ///
+    /// This performs a single type check, making it cheaper than calling
+    /// multiple `to_*` methods in sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # rosy::vm::init().unwrap();
+    /// use rosy::{object::Kind, prelude::*};
+    ///
+    /// let obj = String::from("hello").into_any_object();
+    ///
+    /// match obj.kind() {
+    ///     Kind::String(string) => assert_eq!(string, "hello"),
+    ///     kind => panic!("unexpected {:?}", kind),
+    /// }
+    /// ```
+    #[inline]
+    pub fn kind(self) -> Kind {
+        self.into()
+    }
+
+    /// Returns a deep copy of `self` as owned Rust data.
+    ///
+    /// See [`Value`](../value/enum.Value.html) for how objects are represented.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # rosy::vm::init().unwrap();
+    /// use rosy::{prelude::*, Value};
+    ///
+    /// let hash = Hash::<AnyObject, AnyObject>::new();
+    /// unsafe { hash.insert(Symbol::from("ok"), AnyObject::from(true)) };
+    ///
+    /// let value = hash.into_any_object().to_value();
+    /// let expected = Value::Hash(vec![
+    ///     (Value::Symbol("ok".into()), Value::Bool(true)),
+    /// ]);
+    /// assert_eq!(value, expected);
+    ///
+    /// let class = Class::object().into_any_object();
+    /// assert_eq!(class.to_value(), Value::Other(class));
+    /// ```
+    #[inline]
+    pub fn to_value(self) -> Value {
+        Value::_from_object(self, &mut Vec::new())
+    }
 }
@@ -0,0 +1,542 @@
+use std::{
+    collections::HashMap,
+    convert::TryFrom,
+    error::Error,
+    fmt,
+    hash::{BuildHasher, Hash as StdHash},
+};
+use crate::{
+    enumerator::Enumerator,
+    exception::{ArgumentError, RangeError, TypeError},
+    num::{AnyNumeric, Complex, Rational},
+    prelude::*,
+    string::Encoding,
+    vm::InstrSeq,
+};
+
+/// A type that can be converted from a Ruby object.
+///
+/// This is what allows [`def_method!`] to take arguments of Rust types like
+/// `i64`, `&str` and `Vec<T>`.
+///
+/// # Errors
+///
+/// When conversion fails, an exception is returned:
+///
+/// - `TypeError` if `obj` is not of the expected class. For example,
+///   converting `"hi"` into `i32` results in "no implicit conversion of String
+///   into Integer".
+///
+/// - `RangeError` if `obj` is a number that can't be represented by `Self`.
+///
+/// - `ArgumentError` if `obj` is a `String` that isn't valid UTF-8 when
+///   converting into a Rust string.
+///
+/// # Examples
+///
+/// ```
+/// # rosy::vm::init().unwrap();
+/// use rosy::prelude::*;
+///
+/// let int = AnyObject::from(300);
+///
+/// assert_eq!(i32::from_ruby(&int).unwrap(), 300);
+/// assert!(u8::from_ruby(&int).unwrap_err().is_range_error());
+/// assert!(<&str>::from_ruby(&int).unwrap_err().is_type_error());
+/// ```
+///
+/// [`def_method!`]: ../macro.def_method.html
+pub trait FromRuby<'a>: Sized {
+    /// Converts `obj` into an instance of `Self`.
+    fn from_ruby(obj: &'a AnyObject) -> Result<Self>;
+}
+
+/// A type that can be converted from a Ruby object without borrowing from it.
+///
+/// This is automatically implemented for all types that implement
+/// [`FromRuby`](trait.FromRuby.html) for any lifetime.
+pub trait FromRubyOwned: for<'a> FromRuby<'a> {}
+
+impl<T> FromRubyOwned for T where T: for<'a> FromRuby<'a> {}
+
+/// A type that can be converted into a Ruby object.
+///
+/// This is what allows [`def_method!`] to return Rust types like `f64`,
+/// `Option<T>` and `Vec<T>`.
+///
+/// # Examples
+///
+/// ```
+/// # rosy::vm::init().unwrap();
+/// use rosy::prelude::*;
+///
+/// let array = vec![Some(1), None].into_ruby();
+/// let expected = [AnyObject::from(1), AnyObject::nil()];
+///
+/// assert_eq!(array, &expected[..]);
+/// ```
+///
+/// [`def_method!`]: ../macro.def_method.html
+pub trait IntoRuby {
+    /// Converts `self` into a Ruby object.
+    fn into_ruby(self) -> AnyObject;
+}
+
+// Returns how `obj` is referred to in conversion error messages
+fn _describe(obj: AnyObject) -> std::string::String {
+    if obj.is_nil() {
+        "nil".into()
+    } else if obj.is_true() {
+        "true".into()
+    } else if obj.is_false() {
+        "false".into()
+    } else {
+        obj.class().name().to_string().unwrap_or_default()
+    }
+}
+
+#[cold]
+pub(crate) fn _type_error(obj: AnyObject, expected: &str) -> AnyException {
+    let message = format!(
+        "no implicit conversion of {} into {}",
+        _describe(obj),
+        expected,
+    );
+    TypeError::new(message.as_str()).into_any_exception()
+}
+
+// Returns the unqualified name of `O` without generics (e.g. "Array")
+fn _object_name<O: Object>() -> &'static str {
+    let name = std::any::type_name::<O>();
+    let name = name.split('<').next().unwrap_or(name);
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+/// The error returned when converting an
+/// [`AnyObject`](struct.AnyObject.html) into an object of the wrong type via
+/// `TryFrom`.
+///
+/// This can be converted into a `TypeError` exception, which allows for using
+/// `?` within methods that return an `AnyException`.
+///
+/// # Examples
+///
+/// ```
+/// # rosy::vm::init().unwrap();
+/// use std::convert::TryFrom;
+/// use rosy::prelude::*;
+///
+/// let obj = AnyObject::from(42);
+/// let error = String::try_from(obj).unwrap_err();
+///
+/// assert_eq!(error.expected(), "String");
+/// assert_eq!(error.class_name(), "Integer");
+/// assert_eq!(error.to_string(), "expected String, found Integer (42)");
+///
+/// let exception = AnyException::from(error);
+/// assert!(exception.is_type_error());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CastError {
+    expected: std::string::String,
+    class_name: std::string::String,
+    inspect: std::string::String,
+}
+
+impl fmt::Display for CastError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "expected {}, found {} ({})",
+            self.expected,
+            self.class_name,
+            self.inspect,
+        )
+    }
+}
+
+impl Error for CastError {}
+
+impl From<CastError> for AnyException {
+    #[inline]
+    fn from(error: CastError) -> Self {
+        let message = error.to_string();
+        TypeError::new(message.as_str()).into_any_exception()
+    }
+}
+
+impl CastError {
+    // The maximum number of characters kept from `inspect`, including "..."
+    const INSPECT_LEN: usize = 64;
+
+    // Creates an error for when `obj` could not be cast into `O`
+    #[cold]
+    pub(crate) fn _new<O: Object>(obj: AnyObject) -> Self {
+        Self::_with_expected(obj, _object_name::<O>().into())
+    }
+
+    // Creates an error for when `obj` is not an instance of `expected`
+    #[cold]
+    fn _with_expected(obj: AnyObject, expected: std::string::String) -> Self {
+        let class_name = obj.class().name();
+        // `inspect` may be redefined to raise, in which case the class name
+        // is shown instead
+        let inspect = crate::protected(|| obj.inspect())
+            .map(|inspect| inspect.ellipsized(Self::INSPECT_LEN))
+            .unwrap_or(class_name);
+        let (class_name, inspect) = unsafe {(
+            class_name.to_str_lossy().into_owned(),
+            inspect.to_str_lossy().into_owned(),
+        )};
+        CastError { expected, class_name, inspect }
+    }
+
+    /// Returns the name of the type that was expected, such as `Array`.
+    ///
+    /// For a [`RosyObject<R>`](struct.RosyObject.html), this is the name of
+    /// `R::class()`.
+    #[inline]
+    pub fn expected(&self) -> &str {
+        &self.expected
+    }
+
+    /// Returns the name of the class of the object that failed to be cast.
+    #[inline]
+    pub fn class_name(&self) -> &str {
+        &self.class_name
+    }
+
+    /// Returns the result of calling `inspect` on the object that failed to be
+    /// cast, truncated if it's long.
+    #[inline]
+    pub fn inspect(&self) -> &str {
+        &self.inspect
+    }
+}
+
+macro_rules! impl_try_from {
+    ($($t:ty $(, <$($g:ident),+>)?;)+) => { $(
+        impl$(<$($g: Object),+>)? TryFrom<AnyObject> for $t {
+            type Error = CastError;
+
+            #[inline]
+            fn try_from(obj: AnyObject) -> std::result::Result<Self, CastError> {
+                Self::cast(obj).ok_or_else(|| CastError::_new::<Self>(obj))
+            }
+        }
+    )+ };
+}
+
+impl_try_from! {
+    Array<O>, <O>;
+    Hash<K, V>, <K, V>;
+    Class<O>, <O>;
+    Range<S, E>, <S, E>;
+    AnyException;
+    AnyNumeric;
+    Complex;
+    Encoding;
+    Enumerator;
+    Float;
+    InstrSeq;
+    Integer;
+    Module;
+    Proc;
+    Rational;
+    String;
+    Symbol;
+}
+
+impl<R: Rosy> TryFrom<AnyObject> for RosyObject<R> {
+    type Error = CastError;
+
+    #[inline]
+    fn try_from(obj: AnyObject) -> std::result::Result<Self, CastError> {
+        Self::cast(obj).ok_or_else(|| {
+            let name = R::class().name();
+            let expected = unsafe { name.to_str_lossy().into_owned() };
+            CastError::_with_expected(obj, expected)
+        })
+    }
+}
+
+impl<'a, O: Object> FromRuby<'a> for O {
+    #[inline]
+    fn from_ruby(obj: &'a AnyObject) -> Result<Self> {
+        O::cast(*obj).ok_or_else(|| _type_error(*obj, _object_name::<O>()))
+    }
+}
+
+impl<O: Object> IntoRuby for O {
+    #[inline]
+    fn into_ruby(self) -> AnyObject {
+        self.into()
+    }
+}
+
+macro_rules! impl_int {
+    ($($t:ty)+) => { $(
+        impl FromRuby<'_> for $t {
+            #[inline]
+            fn from_ruby(obj: &AnyObject) -> Result<Self> {
+                let int = obj.to_integer()
+                    .ok_or_else(|| _type_error(*obj, "Integer"))?;
+                int.to_value().ok_or_else(|| _range_error(int, stringify!($t)))
+            }
+        }
+
+        impl IntoRuby for $t {
+            #[inline]
+            fn into_ruby(self) -> AnyObject {
+                Integer::from(self).into()
+            }
+        }
+    )+ };
+}
+
+impl_int! {
+    usize u128 u64 u32 u16 u8
+    isize i128 i64 i32 i16 i8
+}
+
+#[cold]
+fn _range_error(int: Integer, ty: &str) -> AnyException {
+    let message = format!(
+        "integer {} too {} to convert into `{}`",
+        int,
+        if int.is_negative() { "small" } else { "big" },
+        ty,
+    );
+    RangeError::new(message.as_str()).into_any_exception()
+}
+
+impl FromRuby<'_> for f64 {
+    #[inline]
+    fn from_ruby(obj: &AnyObject) -> Result<Self> {
+        if let Some(float) = obj.to_float() {
+            Ok(float.to_f64())
+        } else if let Some(int) = obj.to_integer() {
+            Ok(int.to_f64())
+        } else {
+            Err(_type_error(*obj, "Float"))
+        }
+    }
+}
+
+impl FromRuby<'_> for f32 {
+    #[inline]
+    fn from_ruby(obj: &AnyObject) -> Result<Self> {
+        f64::from_ruby(obj).map(|f| f as f32)
+    }
+}
+
+impl IntoRuby for f64 {
+    #[inline]
+    fn into_ruby(self) -> AnyObject {
+        self.into()
+    }
+}
+
+impl IntoRuby for f32 {
+    #[inline]
+    fn into_ruby(self) -> AnyObject {
+        self.into()
+    }
+}
+
+/// Follows Ruby's rules for truthiness: everything besides `false` and `nil`
+/// converts to `true`.
+impl FromRuby<'_> for bool {
+    #[inline]
+    fn from_ruby(obj: &AnyObject) -> Result<Self> {
+        Ok(crate::util::test_value(obj.raw()))
+    }
+}
+
+impl IntoRuby for bool {
+    #[inline]
+    fn into_ruby(self) -> AnyObject {
+        self.into()
+    }
+}
+
+impl IntoRuby for () {
+    #[inline]
+    fn into_ruby(self) -> AnyObject {
+        AnyObject::nil()
+    }
+}
+
+#[cold]
+fn _utf8_error(error: std::str::Utf8Error) -> AnyException {
+    let message = format!("invalid byte sequence in UTF-8 ({})", error);
+    ArgumentError::new(message.as_str()).into_any_exception()
+}
+
+/// The returned slice borrows the contents of the Ruby string. As with
+/// [`String::to_str`](../string/struct.String.html#method.to_str), the string
+/// must not be mutated while the slice is in use.
+impl<'a> FromRuby<'a> for &'a str {
+    #[inline]
+    fn from_ruby(obj: &'a AnyObject) -> Result<Self> {
+        if !obj.is_string() {
+            return Err(_type_error(*obj, "String"));
+        }
+        unsafe {
+            let string: &'a String = obj.as_unchecked();
+            string.to_str().map_err(_utf8_error)
+        }
+    }
+}
+
+impl FromRuby<'_> for std::string::String {
+    #[inline]
+    fn from_ruby(obj: &AnyObject) -> Result<Self> {
+        <&str>::from_ruby(obj).map(Into::into)
+    }
+}
+
+impl IntoRuby for &str {
+    #[inline]
+    fn into_ruby(self) -> AnyObject {
+        self.into()
+    }
+}
+
+impl IntoRuby for &std::string::String {
+    #[inline]
+    fn into_ruby(self) -> AnyObject {
+        self.into()
+    }
+}
+
+impl IntoRuby for std::string::String {
+    #[inline]
+    fn into_ruby(self) -> AnyObject {
+        (&self).into()
+    }
+}
+
+/// `nil` converts to `None`.
+impl<'a, T: FromRuby<'a>> FromRuby<'a> for Option<T> {
+    #[inline]
+    fn from_ruby(obj: &'a AnyObject) -> Result<Self> {
+        if obj.is_nil() {
+            Ok(None)
+        } else {
+            T::from_ruby(obj).map(Some)
+        }
+    }
+}
+
+/// `None` converts to `nil`.
+impl<T: IntoRuby> IntoRuby for Option<T> {
+    #[inline]
+    fn into_ruby(self) -> AnyObject {
+        match self {
+            Some(value) => value.into_ruby(),
+            None => AnyObject::nil(),
+        }
+    }
+}
+
+impl<T: FromRubyOwned> FromRuby<'_> for Vec<T> {
+    fn from_ruby(obj: &AnyObject) -> Result<Self> {
+        let array = obj.to_array().ok_or_else(|| _type_error(*obj, "Array"))?;
+        array.into_iter().map(|obj| T::from_ruby(&obj)).collect()
+    }
+}
+
+impl<T: IntoRuby> IntoRuby for Vec<T> {
+    #[inline]
+    fn into_ruby(self) -> AnyObject {
+        self.into_iter().map(IntoRuby::into_ruby).collect::<Array>().into()
+    }
+}
+
+impl<K, V, S> FromRuby<'_> for HashMap<K, V, S>
+where
+    K: FromRubyOwned + Eq + StdHash,
+    V: FromRubyOwned,
+    S: BuildHasher + Default,
+{
+    fn from_ruby(obj: &AnyObject) -> Result<Self> {
+        let hash = Hash::<AnyObject, AnyObject>::cast(*obj)
+            .ok_or_else(|| _type_error(*obj, "Hash"))?;
+
+        let mut map = HashMap::with_capacity_and_hasher(
+            hash.len(),
+            S::default(),
+        );
+        hash.try_for_each(|key, val| -> Result<()> {
+            map.insert(K::from_ruby(&key)?, V::from_ruby(&val)?);
+            Ok(())
+        })?;
+        Ok(map)
+    }
+}
+
+impl<K, V, S> IntoRuby for HashMap<K, V, S>
+where
+    K: IntoRuby,
+    V: IntoRuby,
+{
+    #[inline]
+    fn into_ruby(self) -> AnyObject {
+        self.into_iter()
+            .map(|(key, val)| (key.into_ruby(), val.into_ruby()))
+            .collect::<Hash>()
+            .into()
+    }
+}
+
+macro_rules! impl_tuple {
+    ($($n:expr => ($($t:ident)+);)+) => { $(
+        impl<$($t: FromRubyOwned),+> FromRuby<'_> for ($($t,)+) {
+            fn from_ruby(obj: &AnyObject) -> Result<Self> {
+                let array = obj.to_array()
+                    .ok_or_else(|| _type_error(*obj, "Array"))?;
+                if array.len() != $n {
+                    let message = format!(
+                        "wrong array length (given {}, expected {})",
+                        array.len(),
+                        $n,
+                    );
+                    let error = ArgumentError::new(message.as_str());
+                    return Err(error.into_any_exception());
+                }
+                let mut iter = array.into_iter();
+                Ok(($(
+                    $t::from_ruby(&iter.next().unwrap_or_else(AnyObject::nil))?,
+                )+))
+            }
+        }
+
+        impl<$($t: IntoRuby),+> IntoRuby for ($($t,)+) {
+            #[inline]
+            #[allow(non_snake_case)]
+            fn into_ruby(self) -> AnyObject {
+                let ($($t,)+) = self;
+                let array: Array = Array::with_capacity($n);
+                $(unsafe { array.push($t.into_ruby()) };)+
+                array.into()
+            }
+        }
+    )+ };
+}
+
+impl_tuple! {
+    1  => (A);
+    2  => (A B);
+    3  => (A B C);
+    4  => (A B C D);
+    5  => (A B C D E);
+    6  => (A B C D E F);
+    7  => (A B C D E F G);
+    8  => (A B C D E F G H);
+    9  => (A B C D E F G H I);
+    10 => (A B C D E F G H I J);
+    11 => (A B C D E F G H I J K);
+    12 => (A B C D E F G H I J K L);
+}
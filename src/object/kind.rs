@@ -0,0 +1,133 @@
+use crate::{
+    num::{Complex, Rational},
+    object::Ty,
+    prelude::*,
+};
+
+/// The kind of an [`AnyObject`](struct.AnyObject.html), along with the object
+/// cast to its typed wrapper.
+///
+/// This is returned by
+/// [`AnyObject::kind`](struct.AnyObject.html#method.kind) and allows for
+/// handling every kind of object within a single `match`.
+///
+/// # Examples
+///
+/// ```
+/// # rosy::vm::init().unwrap();
+/// use rosy::{object::Kind, prelude::*};
+///
+/// fn describe(obj: AnyObject) -> std::string::String {
+///     match obj.kind() {
+///         Kind::Nil => "nil".into(),
+///         Kind::Fixnum(int) | Kind::Bignum(int) => format!("integer {}", int),
+///         Kind::String(s) => format!("string of {} bytes", s.len()),
+///         Kind::Array(array) => format!("array of {}", array.len()),
+///         _ => format!("instance of {}", obj.class()),
+///     }
+/// }
+///
+/// assert_eq!(describe(AnyObject::nil()), "nil");
+/// assert_eq!(describe(Integer::from(42).into()), "integer 42");
+/// assert_eq!(describe(String::from("hey").into()), "string of 3 bytes");
+/// assert_eq!(describe(Float::from(1.5).into()), "instance of Float");
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub enum Kind {
+    /// `nil`.
+    Nil,
+    /// `true`.
+    True,
+    /// `false`.
+    False,
+    /// An `Integer` that fits in a machine word.
+    Fixnum(Integer),
+    /// An `Integer` that's heap-allocated.
+    Bignum(Integer),
+    /// A `Float`.
+    Float(Float),
+    /// A `Rational`.
+    Rational(Rational),
+    /// A `Complex`.
+    Complex(Complex),
+    /// A `String`.
+    String(String),
+    /// A `Symbol`.
+    Symbol(Symbol),
+    /// An `Array`.
+    Array(Array),
+    /// A `Hash`.
+    Hash(Hash),
+    /// A `Range`.
+    Range(Range),
+    /// A `Class`.
+    Class(Class),
+    /// A `Module` that isn't a `Class`.
+    Module(Module),
+    /// An instance of `Exception` or one of its subclasses.
+    Exception(AnyException),
+    /// An object that wraps native data, such as a
+    /// [`RosyObject`](struct.RosyObject.html).
+    Data(AnyObject),
+    /// An object of any other kind.
+    Object(AnyObject),
+}
+
+impl From<AnyObject> for Kind {
+    #[inline]
+    fn from(obj: AnyObject) -> Self {
+        // Safe because each cast is guarded by a type check
+        unsafe {
+            match obj.ty() {
+                Ty::NIL    => Kind::Nil,
+                Ty::TRUE   => Kind::True,
+                Ty::FALSE  => Kind::False,
+                Ty::FIXNUM => Kind::Fixnum(obj.into_unchecked()),
+                Ty::BIGNUM => Kind::Bignum(obj.into_unchecked()),
+                Ty::FLOAT  => Kind::Float(obj.into_unchecked()),
+                Ty::RATIONAL => Kind::Rational(obj.into_unchecked()),
+                Ty::COMPLEX  => Kind::Complex(obj.into_unchecked()),
+                Ty::STRING => Kind::String(obj.into_unchecked()),
+                Ty::SYMBOL => Kind::Symbol(obj.into_unchecked()),
+                Ty::ARRAY  => Kind::Array(obj.into_unchecked()),
+                Ty::HASH   => Kind::Hash(obj.into_unchecked()),
+                Ty::CLASS  => Kind::Class(obj.into_unchecked()),
+                Ty::MODULE => Kind::Module(obj.into_unchecked()),
+                Ty::DATA   => Kind::Data(obj),
+                // `Range` is implemented as a `Struct` internally
+                Ty::STRUCT if obj.class().inherits(Class::range()) => {
+                    Kind::Range(obj.into_unchecked())
+                },
+                Ty::OBJECT if obj.is_exception() => {
+                    Kind::Exception(obj.into_unchecked())
+                },
+                _ => Kind::Object(obj),
+            }
+        }
+    }
+}
+
+impl Kind {
+    /// Returns the object that `self` was created from.
+    #[inline]
+    pub fn into_any_object(self) -> AnyObject {
+        match self {
+            Kind::Nil => AnyObject::nil(),
+            Kind::True => AnyObject::from(true),
+            Kind::False => AnyObject::from(false),
+            Kind::Fixnum(obj) | Kind::Bignum(obj) => obj.into(),
+            Kind::Float(obj) => obj.into(),
+            Kind::Rational(obj) => obj.into(),
+            Kind::Complex(obj) => obj.into(),
+            Kind::String(obj) => obj.into(),
+            Kind::Symbol(obj) => obj.into(),
+            Kind::Array(obj) => obj.into(),
+            Kind::Hash(obj) => obj.into(),
+            Kind::Range(obj) => obj.into(),
+            Kind::Class(obj) => obj.into(),
+            Kind::Module(obj) => obj.into(),
+            Kind::Exception(obj) => obj.into(),
+            Kind::Data(obj) | Kind::Object(obj) => obj,
+        }
+    }
+}
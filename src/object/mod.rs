@@ -2,6 +2,7 @@
 
 use std::fmt;
 use crate::{
+    enumerator::{Each, EachProtected},
     prelude::*,
     ruby,
     mixin::MethodFn,
@@ -9,19 +10,46 @@ use crate::{
 };
 
 mod any;
+mod convert;
+mod kind;
 mod non_null;
 mod rosy;
 mod ty;
 
 pub(crate) use non_null::NonNullObject;
+pub(crate) use rosy::{_borrow_count, _release_borrows};
 
 #[doc(inline)]
 pub use self::{
     any::AnyObject,
-    rosy::RosyObject,
+    convert::{CastError, FromRuby, FromRubyOwned, IntoRuby},
+    kind::Kind,
+    rosy::{BorrowError, Ref, RefMut, RosyObject},
     ty::Ty,
 };
 
+// Calls `f` with `args` followed by `kwargs` and whether the last argument is
+// keywords; `kwargs` is omitted if empty so that no positional hash is passed
+#[inline]
+pub(crate) fn _with_kwargs<F, O>(
+    args: &[AnyObject],
+    kwargs: Hash<Symbol, AnyObject>,
+    f: F,
+) -> O
+    where F: FnOnce(&[AnyObject], bool) -> O
+{
+    if kwargs.is_empty() {
+        return f(args, false);
+    }
+    // A Ruby array is used rather than a `Vec`, since `f` may raise and skip
+    // freeing it; the array is on the stack and thus kept alive by the GC
+    let all = Array::from_slice(args);
+    unsafe {
+        all.push(kwargs.into_any_object());
+        f(all.as_slice(), true)
+    }
+}
+
 /// Some concrete Ruby object.
 ///
 /// # Safety
@@ -273,6 +301,268 @@ pub unsafe trait Object: Copy
         call_with_protected(self.into(), method.into(), AnyObject::convert_slice(args))
     }
 
+    /// Calls `method` on `self` with `args` and keyword arguments `kwargs`,
+    /// returning its output.
+    ///
+    /// With the `ruby_2_7` feature, `kwargs` is explicitly passed as keywords.
+    /// Otherwise, it's passed as a trailing hash, which Ruby treats as keywords.
+    /// If `kwargs` is empty, only `args` is passed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # rosy::vm::init().unwrap();
+    /// # rosy::protected(|| {
+    /// use std::ffi::CStr;
+    /// use rosy::prelude::*;
+    ///
+    /// let script = b"->(a, b: 2) { a + b }\0";
+    /// let lambda = unsafe {
+    ///     rosy::vm::eval(CStr::from_bytes_with_nul(script).unwrap())
+    /// };
+    ///
+    /// let kwargs: Hash<Symbol, AnyObject> = vec![
+    ///     (Symbol::from("b"), AnyObject::from(40)),
+    /// ].into_iter().collect();
+    ///
+    /// let sum = unsafe {
+    ///     lambda.call_with_kwargs("call", &[Integer::from(2)], kwargs)
+    /// };
+    /// assert_eq!(sum, Integer::from(42));
+    /// # }).unwrap();
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// Calling `method` may void the type safety of `Self`. For example, if one
+    /// calls `push` on `Array<A>` with an object type `B`, then the inserted
+    /// object will be treated as being of type `A`.
+    ///
+    /// An exception will be raised if `method` is not defined on `self` or if
+    /// it doesn't accept `args` and `kwargs`.
+    #[inline]
+    unsafe fn call_with_kwargs(
+        self,
+        method: impl Into<SymbolId>,
+        args: &[impl Object],
+        kwargs: Hash<Symbol, AnyObject>,
+    ) -> AnyObject {
+        // monomorphization
+        unsafe fn call_with_kwargs(
+            object: AnyObject,
+            method: SymbolId,
+            args: &[AnyObject],
+            kwargs: Hash<Symbol, AnyObject>,
+        ) -> AnyObject {
+            _with_kwargs(args, kwargs, |args, _kw_splat| {
+                #[cfg(feature = "ruby_2_7")]
+                let raw = ruby::rb_funcallv_kw(
+                    object.raw(),
+                    method.raw(),
+                    args.len() as _,
+                    args.as_ptr() as _,
+                    _kw_splat as _,
+                );
+                #[cfg(not(feature = "ruby_2_7"))]
+                let raw = ruby::rb_funcallv(
+                    object.raw(),
+                    method.raw(),
+                    args.len() as _,
+                    args.as_ptr() as _,
+                );
+                AnyObject::from_raw(raw)
+            })
+        }
+        let args = AnyObject::convert_slice(args);
+        call_with_kwargs(self.into(), method.into(), args, kwargs)
+    }
+
+    /// Calls `method` on `self` with `args` and keyword arguments `kwargs`,
+    /// returning its output or an exception if one is raised.
+    ///
+    /// See [`call_with_kwargs`](#method.call_with_kwargs) for more info.
+    ///
+    /// # Safety
+    ///
+    /// Calling `method` may void the type safety of `Self`. For example, if one
+    /// calls `push` on `Array<A>` with an object type `B`, then the inserted
+    /// object will be treated as being of type `A`.
+    #[inline]
+    unsafe fn call_with_kwargs_protected(
+        self,
+        method: impl Into<SymbolId>,
+        args: &[impl Object],
+        kwargs: Hash<Symbol, AnyObject>,
+    ) -> Result<AnyObject> {
+        let method = method.into();
+        let args = AnyObject::convert_slice(args);
+        crate::protected_no_panic(|| self.call_with_kwargs(method, args, kwargs))
+    }
+
+    /// Calls `method` on `self` with `args` and `f` as its block, returning the
+    /// method's output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # rosy::vm::init().unwrap();
+    /// use rosy::prelude::*;
+    ///
+    /// let array: Array = (1..=4).map(Integer::from).collect();
+    /// let args: &[AnyObject] = &[];
+    ///
+    /// let mut sum = 0;
+    /// unsafe {
+    ///     array.call_with_block("each", args, |args| {
+    ///         sum += args[0].to_integer().unwrap().to_value::<i64>().unwrap();
+    ///         AnyObject::nil()
+    ///     });
+    /// }
+    /// assert_eq!(sum, 10);
+    ///
+    /// let sorted = unsafe {
+    ///     array.call_with_block("sort_by", args, |args| {
+    ///         let int = args[0].to_integer().unwrap();
+    ///         Integer::from(-int.to_value::<i64>().unwrap())
+    ///     })
+    /// };
+    /// let expected: Array = (1..=4).rev().map(Integer::from).collect();
+    /// assert_eq!(sorted, expected);
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// Calling `method` may void the type safety of `Self`. For example, if one
+    /// calls `push` on `Array<A>` with an object type `B`, then the inserted
+    /// object will be treated as being of type `A`.
+    ///
+    /// An exception will be raised if `method` is not defined on `self`. If `f`
+    /// panics, the panic is raised as an exception and resumed once it reaches
+    /// a protected call made from Rust.
+    ///
+    /// `f` is only valid for the duration of the call. The caller must ensure
+    /// that `method` doesn't keep the block to call it later, as is done by
+    /// `define_method`, `Hash.new`, `to_enum` or by storing `&block` in a
+    /// variable. Use a [`Proc`](../proc/struct.Proc.html) for a closure that
+    /// outlives the call.
+    #[inline]
+    unsafe fn call_with_block<F, O>(
+        self,
+        method: impl Into<SymbolId>,
+        args: &[impl Object],
+        mut f: F,
+    ) -> AnyObject
+    where
+        F: FnMut(&[AnyObject]) -> O,
+        O: Into<AnyObject>,
+    {
+        let mut f = move |args: &[AnyObject]| f(args).into();
+        crate::proc::_call_with_block(
+            self.into(),
+            method.into(),
+            AnyObject::convert_slice(args),
+            &mut f,
+        )
+    }
+
+    /// Calls `method` on `self` with `args` and `f` as its block, returning the
+    /// method's output or an exception if one is raised.
+    ///
+    /// # Safety
+    ///
+    /// Calling `method` may void the type safety of `Self`. For example, if one
+    /// calls `push` on `Array<A>` with an object type `B`, then the inserted
+    /// object will be treated as being of type `A`.
+    ///
+    /// `f` is only valid for the duration of the call. The caller must ensure
+    /// that `method` doesn't keep the block to call it later, as is done by
+    /// `define_method`, `Hash.new`, `to_enum` or by storing `&block` in a
+    /// variable. Use a [`Proc`](../proc/struct.Proc.html) for a closure that
+    /// outlives the call.
+    #[inline]
+    unsafe fn call_with_block_protected<F, O>(
+        self,
+        method: impl Into<SymbolId>,
+        args: &[impl Object],
+        f: F,
+    ) -> Result<AnyObject>
+    where
+        F: FnMut(&[AnyObject]) -> O,
+        O: Into<AnyObject>,
+    {
+        crate::protected_no_panic(|| self.call_with_block(method, args, f))
+    }
+
+    /// Returns an iterator over the items yielded by `each` on `self`.
+    ///
+    /// This uses external enumeration via an
+    /// [`Enumerator`](../enumerator/struct.Enumerator.html), ending once
+    /// `StopIteration` is raised.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # rosy::vm::init().unwrap();
+    /// use rosy::prelude::*;
+    ///
+    /// let range = Range::<Integer>::new(1..4).unwrap();
+    /// let items: Vec<AnyObject> = unsafe { range.each().collect() };
+    ///
+    /// assert_eq!(items, [1, 2, 3]);
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// Code executed from `each` may void the type safety of objects accessible
+    /// from Rust. For example, if one calls `push` on an `Array<A>` with an
+    /// object of type `B`, then the inserted object will be treated as being of
+    /// type `A`.
+    ///
+    /// An exception other than `StopIteration` that is raised during
+    /// enumeration will be raised from the iterator.
+    #[inline]
+    unsafe fn each(self) -> Each {
+        let args: &[AnyObject] = &[];
+        Enumerator::new(self, "each", args).iter()
+    }
+
+    /// Returns an iterator over the items yielded by `each` on `self`, or an
+    /// exception if one is raised during enumeration.
+    ///
+    /// The iterator ends after yielding an exception.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # rosy::vm::init().unwrap();
+    /// # rosy::protected(|| {
+    /// use std::ffi::CStr;
+    /// use rosy::prelude::*;
+    ///
+    /// let script = b"Enumerator.new { |y| y << 1; raise 'oh no' }\0";
+    /// let script = CStr::from_bytes_with_nul(script).unwrap();
+    ///
+    /// let enumerator = unsafe { rosy::vm::eval(script) };
+    /// let mut iter = unsafe { enumerator.each_protected() };
+    ///
+    /// assert_eq!(iter.next().unwrap().unwrap(), 1);
+    /// assert!(iter.next().unwrap().unwrap_err().is_runtime_error());
+    /// assert!(iter.next().is_none());
+    /// # }).unwrap();
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// Code executed from `each` may void the type safety of objects accessible
+    /// from Rust. For example, if one calls `push` on an `Array<A>` with an
+    /// object of type `B`, then the inserted object will be treated as being of
+    /// type `A`.
+    #[inline]
+    unsafe fn each_protected(self) -> EachProtected {
+        let args: &[AnyObject] = &[];
+        Enumerator::new(self, "each", args).iter_protected()
+    }
+
     /// Calls the public `method` on `self` and returns its output.
     ///
     /// # Safety
@@ -499,8 +789,12 @@ mod tests {
         let ids: &[(&str, _)] = &ids! {
             AnyException,
             AnyObject,
+            crate::num::AnyNumeric,
+            Enumerator,
             Float,
             Integer,
+            crate::num::Complex,
+            crate::num::Rational,
             String,
             Symbol,
             crate::vm::InstrSeq,
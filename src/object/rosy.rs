@@ -1,10 +1,15 @@
 use std::{
-    ffi::c_void,
+    cell::{Cell, RefCell},
+    error::Error,
+    ffi::{c_void, CStr},
     fmt,
     marker::PhantomData,
+    mem,
+    ops::{Deref, DerefMut},
     ptr,
 };
 use crate::{
+    exception::RuntimeError,
     object::NonNullObject,
     prelude::*,
     ruby::{self, rb_data_type_t, rb_data_type_t_function},
@@ -13,6 +18,17 @@ use crate::{
 /// An instance of a Ruby object that wraps around Rust data.
 ///
 /// See the documentation for `Rosy` for more information.
+///
+/// # Borrowing
+///
+/// Because `RosyObject` is `Copy` and its data is reachable from Ruby, access
+/// to the data is checked at runtime in the same way as with
+/// [`RefCell`](https://doc.rust-lang.org/std/cell/struct.RefCell.html). See
+/// [`borrow`](#method.borrow) and [`borrow_mut`](#method.borrow_mut).
+///
+/// Borrows are released if a Ruby exception, `break` or `throw` skips the drop
+/// of their guard, so long as it happens within a method defined via Rosy or a
+/// [`protected`](../fn.protected.html) call.
 #[repr(transparent)]
 pub struct RosyObject<R> {
     inner: NonNullObject,
@@ -86,12 +102,7 @@ unsafe impl<R: Rosy> Object for RosyObject<R> {
 impl<R: Rosy> From<Box<R>> for RosyObject<R> {
     #[inline]
     fn from(rosy: Box<R>) -> Self {
-        let rosy = Box::into_raw(rosy) as *mut c_void;
-        let ty = RosyObject::<R>::data_type();
-        let class = R::class().raw();
-        unsafe {
-            Self::from_raw(ruby::rb_data_typed_object_wrap(class, rosy, ty))
-        }
+        unsafe { Self::_wrap(rosy, R::class()) }
     }
 }
 
@@ -119,14 +130,25 @@ impl<R: Rosy> fmt::Display for RosyObject<R> {
 impl<R: Rosy> RosyObject<R> {
     #[inline]
     pub(crate) fn data_type() -> &'static rb_data_type_t {
-        unsafe extern "C" fn dmark<R: Rosy>(rosy: *mut c_void) {
-            (&mut *(rosy as *mut R)).mark();
+        // Instances allocated by Ruby have no data until initialized
+        unsafe extern "C" fn dmark<R: Rosy>(data: *mut c_void) {
+            // This must happen even while the data is mutably borrowed, since
+            // the borrower may be what triggered garbage collection
+            if let Some(data) = (data as *const RosyData<R>).as_ref() {
+                (*data.rosy).mark();
+            }
         }
-        unsafe extern "C" fn dfree<R: Rosy>(rosy: *mut c_void) {
-            Box::from_raw(rosy as *mut R).free();
+        unsafe extern "C" fn dfree<R: Rosy>(data: *mut c_void) {
+            if !data.is_null() {
+                let data = Box::from_raw(data as *mut RosyData<R>);
+                Box::from_raw(data.rosy).free();
+            }
         }
-        unsafe extern "C" fn dsize<R: Rosy>(rosy: *const c_void) -> usize {
-            (&*(rosy as *const R)).size()
+        unsafe extern "C" fn dsize<R: Rosy>(data: *const c_void) -> usize {
+            match (data as *const RosyData<R>).as_ref() {
+                Some(data) => mem::size_of_val(data) + (*data.rosy).size(),
+                None => 0,
+            }
         }
         &rb_data_type_t {
             wrap_struct_name: R::ID,
@@ -142,19 +164,479 @@ impl<R: Rosy> RosyObject<R> {
         }
     }
 
+    #[inline]
+    unsafe fn _wrap(rosy: Box<R>, class: Class) -> Self {
+        let data = RosyData::new(rosy) as *mut c_void;
+        let ty = RosyObject::<R>::data_type();
+        Self::from_raw(ruby::rb_data_typed_object_wrap(class.raw(), data, ty))
+    }
+
+    /// Creates a new instance of `class` that wraps `rosy`, rather than an
+    /// instance of `R::class()`.
+    ///
+    /// # Safety
+    ///
+    /// Methods defined on `class` (and its ancestors) must expect instances to
+    /// hold Rust data of type `R`. For example, wrapping data in an instance
+    /// of `String` results in undefined behavior once any `String` method is
+    /// called on it.
+    #[inline]
+    pub unsafe fn with_class(rosy: R, class: Class) -> Self {
+        Self::_wrap(Box::new(rosy), class)
+    }
+
+    // Whether `obj` is a typed data object for `R`, which is determined by
+    // comparing its type name against `R::ID`
+    fn _is_typed(obj: AnyObject) -> bool {
+        use crate::object::Ty;
+
+        if !crate::util::value_is_built_in_ty(obj.raw(), Ty::DATA) {
+            return false;
+        }
+        unsafe {
+            let typed_data = obj.raw() as *const ruby::RTypedData;
+            if (*typed_data).typed_flag != 1 {
+                return false;
+            }
+            let ty = (*typed_data).type_;
+            ptr::eq(ty, Self::data_type()) ||
+            CStr::from_ptr((*ty).wrap_struct_name) == CStr::from_ptr(R::ID)
+        }
+    }
+
+    /// Casts `obj` if it wraps initialized data of a type with the same
+    /// [`ID`](../trait.Rosy.html#associatedconstant.ID) as `R`.
+    ///
+    /// Unlike the default [`Rosy::cast`](../trait.Rosy.html#method.cast), this
+    /// recognizes instances created from Ruby, such as via
+    /// [`Class::def_initialize`](../struct.Class.html#method.def_initialize).
+    /// It's meant to be called from an implementation of `Rosy::cast`, which
+    /// is what `#[derive(Rosy)]` generates.
+    ///
+    /// # Safety
+    ///
+    /// No other type may use the same `ID` as `R`. This includes other
+    /// instances of a generic type and the same type with different lifetimes,
+    /// since those can't be told apart at runtime.
+    #[inline]
+    pub unsafe fn cast_by_id<A: Object>(obj: A) -> Option<Self> {
+        let is_instance = crate::rosy::_is_same_type::<R, A>() ||
+            Self::_is_instance(obj.into_any_object());
+        if is_instance {
+            Some(Self::cast_unchecked(obj))
+        } else {
+            None
+        }
+    }
+
+    // Whether `obj` wraps an initialized `R`
+    #[inline]
+    pub(crate) fn _is_instance(obj: AnyObject) -> bool {
+        Self::_is_typed(obj) && unsafe {
+            !(*(obj.raw() as *const ruby::RTypedData)).data.is_null()
+        }
+    }
+
+    // Allocates an instance of `class` with no data, which is expected to be
+    // set by `initialize`
+    unsafe extern "C" fn _alloc(class: ruby::VALUE) -> ruby::VALUE {
+        ruby::rb_data_typed_object_wrap(class, ptr::null_mut(), Self::data_type())
+    }
+
+    #[inline]
+    pub(crate) fn _def_alloc_func(class: Class) {
+        unsafe { ruby::rb_define_alloc_func(class.raw(), Some(Self::_alloc)) };
+    }
+
+    // Sets the data of `obj` that was allocated by Ruby via `_alloc`
+    #[doc(hidden)]
+    pub fn _initialize(obj: AnyObject, rosy: R) -> Result {
+        use crate::exception::NameError;
+
+        if !Self::_is_typed(obj) {
+            return Err(Self::_class_type_error(obj));
+        }
+        unsafe {
+            let typed_data = obj.raw() as *mut ruby::RTypedData;
+            if !(*typed_data).data.is_null() {
+                let error = NameError::new("`initialize' called twice");
+                return Err(error.into_any_exception());
+            }
+            (*typed_data).data = RosyData::new(Box::new(rosy)) as *mut c_void;
+        }
+        Ok(())
+    }
+
+    /// Returns whether `self` holds Rust data.
+    ///
+    /// This is only `false` for an instance allocated by Ruby (such as via
+    /// `allocate`) that has yet to be initialized.
+    #[inline]
+    pub fn is_initialized(self) -> bool {
+        !self.data().is_null()
+    }
+
+    // A `TypeError` for when `obj` isn't an instance of `R::class()`
+    #[cold]
+    fn _class_type_error(obj: AnyObject) -> AnyException {
+        let class = R::class().name().to_string().unwrap_or_default();
+        super::convert::_type_error(obj, &class)
+    }
+
+    // Compares the data of `this` and `other` for `==` and `eql?`, which is
+    // `false` if `other` isn't an instance of `R`
+    pub(crate) fn _data_eq(this: AnyObject, other: AnyObject) -> Result<bool>
+        where R: PartialEq
+    {
+        let this = Self::_cast_instance(this)?;
+        if this.raw() == other.raw() {
+            return Ok(true);
+        }
+        match Self::_cast_instance(other) {
+            Ok(other) => Ok(*this.borrow()? == *other.borrow()?),
+            Err(_) => Ok(false),
+        }
+    }
+
+    // Casts `obj` to an initialized instance, raising a `TypeError` naming
+    // `R::class()` if it isn't one
+    pub(crate) fn _cast_instance(obj: AnyObject) -> Result<Self> {
+        if Self::_is_instance(obj) {
+            unsafe { Ok(Self::cast_unchecked(obj)) }
+        } else {
+            Err(Self::_class_type_error(obj))
+        }
+    }
+
+    // Casts the receiver of a method defined via `#[rosy::class]`, raising a
+    // `TypeError` if it isn't an instance of `R`
+    #[doc(hidden)]
+    pub fn _cast_self(obj: AnyObject, class_name: &str) -> Result<Self> {
+        if Self::_is_instance(obj) {
+            unsafe { Ok(Self::cast_unchecked(obj)) }
+        } else {
+            Err(super::convert::_type_error(obj, class_name))
+        }
+    }
+
     #[inline]
     fn r_typed_data(self) -> *mut ruby::RTypedData {
         self.raw() as *mut ruby::RTypedData
     }
 
     #[inline]
-    fn data(self) -> *mut R {
-        unsafe { (*self.r_typed_data()).data as *mut R }
+    fn data(self) -> *mut RosyData<R> {
+        unsafe { (*self.r_typed_data()).data as *mut RosyData<R> }
+    }
+
+    #[inline]
+    fn initialized_data(&self) -> Result<&RosyData<R>, BorrowError> {
+        unsafe { self.data().as_ref().ok_or(BorrowError::Uninitialized) }
+    }
+
+    /// Returns a reference to the inner `Rosy` value without checking whether
+    /// it's mutably borrowed.
+    ///
+    /// # Safety
+    ///
+    /// The data must not be mutably borrowed via
+    /// [`borrow_mut`](#method.borrow_mut) while the returned reference is
+    /// alive.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` [is not initialized](#method.is_initialized).
+    #[inline]
+    pub unsafe fn as_data(&self) -> &R {
+        match self.initialized_data() {
+            Ok(data) => &*data.rosy,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    /// Immutably borrows the inner `Rosy` value until the returned `Ref` is
+    /// dropped.
+    ///
+    /// Multiple immutable borrows can exist at the same time.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the data is mutably borrowed or if `self` [is
+    /// not initialized](#method.is_initialized).
+    #[inline]
+    pub fn borrow(&self) -> Result<Ref<'_, R>, BorrowError> {
+        let data = self.initialized_data()?;
+        let borrow = data.borrow.get();
+        if borrow < 0 {
+            return Err(BorrowError::MutablyBorrowed);
+        }
+        data.borrow.set(borrow + 1);
+        _track(&data.borrow);
+        Ok(Ref { data })
+    }
+
+    /// Mutably borrows the inner `Rosy` value until the returned `RefMut` is
+    /// dropped.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the data is borrowed in any way or if `self`
+    /// [is not initialized](#method.is_initialized).
+    ///
+    /// # Examples
+    ///
+    /// A conflicting borrow results in an error, which converts to a
+    /// `RuntimeError` exception. This allows for raising it from a Rust-defined
+    /// method rather than panicking.
+    ///
+    /// ```
+    /// # rosy::vm::init().unwrap();
+    /// use std::os::raw::c_char;
+    /// use rosy::{object::BorrowError, prelude::*};
+    ///
+    /// #[derive(Debug)]
+    /// struct Count(u32);
+    ///
+    /// unsafe impl Rosy for Count {
+    ///     const ID: *const c_char = b"rosy_count\0".as_ptr() as _;
+    ///
+    ///     fn mark(&self) {}
+    /// }
+    ///
+    /// let count = RosyObject::from(Count(0));
+    /// {
+    ///     let mut data = count.borrow_mut().unwrap();
+    ///     data.0 += 1;
+    ///
+    ///     let error = count.borrow().unwrap_err();
+    ///     assert_eq!(error, BorrowError::MutablyBorrowed);
+    ///     assert!(AnyException::from(error).is_runtime_error());
+    /// }
+    /// assert_eq!(count.borrow().unwrap().0, 1);
+    /// ```
+    ///
+    /// The borrow is also released if an exception or other jump skips
+    /// dropping the `RefMut`, such as when a method raises while holding it:
+    ///
+    /// ```
+    /// # rosy::vm::init().unwrap();
+    /// use std::os::raw::c_char;
+    /// use rosy::prelude::*;
+    ///
+    /// struct Count(u32);
+    ///
+    /// unsafe impl Rosy for Count {
+    ///     const ID: *const c_char = b"rosy_raising_count\0".as_ptr() as _;
+    ///
+    ///     fn class() -> Class {
+    ///         Class::get_or_def("RaisingCount").unwrap()
+    ///     }
+    ///
+    ///     fn mark(&self) {}
+    /// }
+    ///
+    /// let class = Count::class();
+    ///
+    /// rosy::def_method!(class, "increment!", |this| -> Result<AnyObject> {
+    ///     let this = RosyObject::<Count>::cast(this).unwrap();
+    ///     let mut data = this.borrow_mut()?;
+    ///     data.0 += 1;
+    ///
+    ///     // Raises a `NoMethodError` while `data` is alive
+    ///     Ok(unsafe { this.call("undefined_method") })
+    /// }).unwrap();
+    ///
+    /// let count = RosyObject::from(Count(0));
+    ///
+    /// let error = unsafe { count.call_protected("increment!").unwrap_err() };
+    /// assert!(error.is_no_method_error());
+    ///
+    /// assert_eq!(count.borrow_mut().unwrap().0, 1);
+    /// ```
+    #[inline]
+    pub fn borrow_mut(&self) -> Result<RefMut<'_, R>, BorrowError> {
+        let data = self.initialized_data()?;
+        match data.borrow.get() {
+            0 => {},
+            b if b < 0 => return Err(BorrowError::MutablyBorrowed),
+            _ => return Err(BorrowError::Borrowed),
+        }
+        data.borrow.set(-1);
+        _track(&data.borrow);
+        Ok(RefMut { data })
     }
+}
+
+// The Rust data behind a `RosyObject`, along with its borrow state
+struct RosyData<R> {
+    // The number of active `Ref`s, or -1 if there's an active `RefMut`
+    borrow: Cell<isize>,
+    rosy: *mut R,
+}
+
+impl<R> RosyData<R> {
+    #[inline]
+    fn new(rosy: Box<R>) -> *mut Self {
+        let data = RosyData { borrow: Cell::new(0), rosy: Box::into_raw(rosy) };
+        Box::into_raw(Box::new(data))
+    }
+}
+
+thread_local! {
+    // The borrow flags of the `Ref`s and `RefMut`s that are alive, so that they
+    // can be released if a Ruby jump skips their destructors
+    static BORROWS: RefCell<Vec<*const Cell<isize>>> = RefCell::default();
+}
+
+#[inline]
+fn _track(flag: &Cell<isize>) {
+    BORROWS.with(|borrows| borrows.borrow_mut().push(flag));
+}
+
+#[inline]
+fn _untrack(flag: &Cell<isize>) {
+    BORROWS.with(|borrows| {
+        let mut borrows = borrows.borrow_mut();
+        let flag = flag as *const Cell<isize>;
+        if let Some(index) = borrows.iter().rposition(|&f| f == flag) {
+            borrows.remove(index);
+        }
+    });
+}
+
+// Undoes a single `Ref` or `RefMut`, which are told apart by the sign of `flag`
+#[inline]
+fn _release(flag: &Cell<isize>) {
+    match flag.get() {
+        b if b < 0 => flag.set(0),
+        b => flag.set(b - 1),
+    }
+}
+
+// Returns the number of borrows that are alive, to later be passed into
+// `_release_borrows`
+#[inline]
+pub(crate) fn _borrow_count() -> usize {
+    BORROWS.with(|borrows| borrows.borrow().len())
+}
+
+// Releases the borrows made since there were `count`, after a jump skipped the
+// destructors of their guards
+//
+// This must be called right after the jump, while the borrowed objects are
+// still reachable from the stack
+#[cold]
+pub(crate) unsafe fn _release_borrows(count: usize) {
+    BORROWS.with(|borrows| {
+        let mut borrows = borrows.borrow_mut();
+        if count < borrows.len() {
+            for &flag in borrows[count..].iter().rev() {
+                _release(&*flag);
+            }
+            borrows.truncate(count);
+        }
+    });
+}
+
+/// An immutable borrow of the data in a [`RosyObject`](struct.RosyObject.html),
+/// returned by [`RosyObject::borrow`](struct.RosyObject.html#method.borrow).
+pub struct Ref<'a, R> {
+    data: &'a RosyData<R>,
+}
+
+impl<R> Drop for Ref<'_, R> {
+    #[inline]
+    fn drop(&mut self) {
+        _untrack(&self.data.borrow);
+        _release(&self.data.borrow);
+    }
+}
+
+impl<R> Deref for Ref<'_, R> {
+    type Target = R;
+
+    #[inline]
+    fn deref(&self) -> &R {
+        unsafe { &*self.data.rosy }
+    }
+}
+
+impl<R: fmt::Debug> fmt::Debug for Ref<'_, R> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+/// A mutable borrow of the data in a [`RosyObject`](struct.RosyObject.html),
+/// returned by [`RosyObject::borrow_mut`](struct.RosyObject.html#method.borrow_mut).
+pub struct RefMut<'a, R> {
+    data: &'a RosyData<R>,
+}
+
+impl<R> Drop for RefMut<'_, R> {
+    #[inline]
+    fn drop(&mut self) {
+        _untrack(&self.data.borrow);
+        _release(&self.data.borrow);
+    }
+}
+
+impl<R> Deref for RefMut<'_, R> {
+    type Target = R;
+
+    #[inline]
+    fn deref(&self) -> &R {
+        unsafe { &*self.data.rosy }
+    }
+}
+
+impl<R> DerefMut for RefMut<'_, R> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut R {
+        unsafe { &mut *self.data.rosy }
+    }
+}
+
+impl<R: fmt::Debug> fmt::Debug for RefMut<'_, R> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+/// An error returned when the data in a [`RosyObject`](struct.RosyObject.html)
+/// can't be borrowed.
+///
+/// This converts to a `RuntimeError` exception.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BorrowError {
+    /// The data is mutably borrowed.
+    MutablyBorrowed,
+    /// The data is immutably borrowed and thus can't be mutably borrowed.
+    Borrowed,
+    /// The object was allocated by Ruby and has yet to be initialized.
+    Uninitialized,
+}
+
+impl fmt::Display for BorrowError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self {
+            BorrowError::MutablyBorrowed => "already mutably borrowed",
+            BorrowError::Borrowed => "already borrowed",
+            BorrowError::Uninitialized => "uninitialized data",
+        };
+        f.write_str(message)
+    }
+}
+
+impl Error for BorrowError {}
 
-    /// Returns a reference to the inner `Rosy` value.
+impl From<BorrowError> for AnyException {
     #[inline]
-    pub fn as_data(&self) -> &R {
-        unsafe { &*self.data() }
+    fn from(error: BorrowError) -> Self {
+        let message = error.to_string();
+        RuntimeError::new(message.as_str()).into_any_exception()
     }
 }
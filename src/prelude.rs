@@ -18,14 +18,21 @@
 #[doc(no_inline)]
 pub use crate::{
     array::Array,
+    enumerator::Enumerator,
     exception::{AnyException, Exception},
     hash::Hash,
     mixin::{Mixin, Class, Module},
     num::{Float, Integer},
-    object::{AnyObject, Object, RosyObject},
+    object::{AnyObject, FromRuby, FromRubyOwned, IntoRuby, Object, RosyObject},
+    proc::Proc,
     range::Range,
     Result,
-    rosy::Rosy,
+    rosy::{Initialize, Rosy},
     string::String,
     symbol::{Symbol, SymbolId},
+    value::Value,
 };
+
+#[cfg(feature = "macros")]
+#[doc(no_inline)]
+pub use rosy_macros::Rosy;
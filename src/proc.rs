@@ -0,0 +1,336 @@
+//! Ruby procs.
+
+use std::{
+    cell::RefCell,
+    fmt,
+    os::raw::{c_char, c_int},
+    slice,
+};
+use crate::{
+    exception::RuntimeError,
+    object::NonNullObject,
+    prelude::*,
+    ruby,
+};
+
+/// An instance of Ruby's `Proc` class.
+///
+/// # Examples
+///
+/// A `Proc` can be created from a Rust closure, which may hold on to its own
+/// state across calls:
+///
+/// ```
+/// # rosy::vm::init().unwrap();
+/// use rosy::{prelude::*, Proc};
+///
+/// let mut total = 0;
+/// let adder = Proc::new(move |args| {
+///     for arg in args {
+///         total += arg.to_integer().and_then(|i| i.to_value::<i64>()).unwrap();
+///     }
+///     Integer::from(total)
+/// });
+///
+/// let args = [Integer::from(1), Integer::from(2)];
+/// unsafe {
+///     assert_eq!(adder.invoke(&args), Integer::from(3));
+///     assert_eq!(adder.invoke(&args), Integer::from(6));
+/// }
+/// ```
+///
+/// Because it's a real Ruby `Proc`, it can be passed to Ruby code that expects
+/// a block:
+///
+/// ```
+/// # rosy::vm::init().unwrap();
+/// # rosy::protected(|| {
+/// use std::ffi::CStr;
+/// use rosy::{prelude::*, Proc};
+///
+/// let double = Proc::new(|args| {
+///     let int = args[0].to_integer().unwrap();
+///     Integer::from(int.to_value::<i64>().unwrap() * 2)
+/// });
+/// let array: Array = (1..4).map(Integer::from).collect();
+///
+/// let doubled = unsafe {
+///     let script = b"->(array, f) { array.map(&f) }\0";
+///     let map = rosy::vm::eval(CStr::from_bytes_with_nul(script).unwrap());
+///     map.call_with("call", &[array.into_any_object(), double.into()])
+/// };
+///
+/// let expected: Array = (1..4).map(|i| Integer::from(i * 2)).collect();
+/// assert_eq!(doubled, expected);
+/// # }).unwrap();
+/// ```
+///
+/// If the closure panics, the panic unwinds through Ruby as an exception and is
+/// resumed once it reaches a protected call made from Rust:
+///
+/// ```
+/// # rosy::vm::init().unwrap();
+/// use rosy::{AnyObject, Proc};
+///
+/// let proc = Proc::new(|_| -> AnyObject { panic!("oh no") });
+///
+/// std::panic::catch_unwind(|| {
+///     let args: &[AnyObject] = &[];
+///     unsafe { proc.invoke_protected(args) }
+/// }).unwrap_err();
+/// ```
+#[derive(Clone, Copy, Debug)]
+#[repr(transparent)]
+pub struct Proc(NonNullObject);
+
+impl AsRef<AnyObject> for Proc {
+    #[inline]
+    fn as_ref(&self) -> &AnyObject { self.0.as_ref() }
+}
+
+impl From<Proc> for AnyObject {
+    #[inline]
+    fn from(obj: Proc) -> Self { obj.0.into() }
+}
+
+impl PartialEq<AnyObject> for Proc {
+    #[inline]
+    fn eq(&self, obj: &AnyObject) -> bool {
+        self.as_any_object() == obj
+    }
+}
+
+unsafe impl Object for Proc {
+    #[inline]
+    fn unique_id() -> Option<u128> {
+        Some((!0) - 2)
+    }
+
+    #[inline]
+    fn cast<A: Object>(obj: A) -> Option<Self> {
+        let is_proc = unsafe { ruby::rb_obj_is_proc(obj.raw()) };
+        if is_proc == crate::util::TRUE_VALUE {
+            unsafe { Some(Self::cast_unchecked(obj)) }
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for Proc {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_any_object().fmt(f)
+    }
+}
+
+impl Proc {
+    /// Creates a new `Proc` that calls `f` with its arguments when invoked.
+    ///
+    /// The closure is owned by the `Proc` and is dropped once the `Proc` is
+    /// garbage collected.
+    ///
+    /// **Note:** Ruby objects captured by `f` are not marked by the garbage
+    /// collector. They must be kept alive some other way, such as by being
+    /// referenced by another live object or via
+    /// [`gc::register`](gc/fn.register.html).
+    #[inline]
+    pub fn new<F, O>(mut f: F) -> Self
+    where
+        F: FnMut(&[AnyObject]) -> O + 'static,
+        O: Into<AnyObject>,
+    {
+        Self::_new(Box::new(move |args| f(args).into()))
+    }
+
+    // monomorphization
+    fn _new(f: Box<RawProcFn>) -> Self {
+        let data = RosyObject::from(ProcFn(RefCell::new(f)));
+        unsafe {
+            let raw = ruby::rb_proc_new(Some(call_proc_fn), data.raw());
+            Self::from_raw(raw)
+        }
+    }
+
+    /// Returns the block given to the current method as a `Proc`, if any.
+    ///
+    /// This is equivalent to taking a `&block` parameter in Ruby.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # rosy::vm::init().unwrap();
+    /// # rosy::protected(|| {
+    /// use std::ffi::CStr;
+    /// use rosy::{prelude::*, Proc};
+    ///
+    /// let class = Class::object();
+    ///
+    /// rosy::def_method!(class, "to_block_proc", |_this| {
+    ///     Proc::from_block()
+    /// }).unwrap();
+    ///
+    /// let script = b"to_block_proc { |x| x * 2 }\0";
+    /// let script = CStr::from_bytes_with_nul(script).unwrap();
+    ///
+    /// let proc = unsafe { rosy::vm::eval(script) };
+    /// let proc = Proc::cast(proc).unwrap();
+    ///
+    /// let result = unsafe { proc.invoke(&[Integer::from(21)]) };
+    /// assert_eq!(result, Integer::from(42));
+    /// # }).unwrap();
+    /// ```
+    #[inline]
+    pub fn from_block() -> Option<Self> {
+        if AnyObject::block_given() {
+            unsafe { Some(Self::from_raw(ruby::rb_block_proc())) }
+        } else {
+            None
+        }
+    }
+
+    /// Calls `self` with `args` and returns its output.
+    ///
+    /// # Safety
+    ///
+    /// Code executed from `self` may void the type safety of objects accessible
+    /// from Rust. For example, if one calls `push` on an `Array<A>` with an
+    /// object of type `B`, then the inserted object will be treated as being of
+    /// type `A`.
+    ///
+    /// An exception will be raised if `self` raises one or if `args` does not
+    /// match what is expected by a lambda.
+    #[inline]
+    pub unsafe fn invoke(self, args: &[impl Object]) -> AnyObject {
+        AnyObject::from_raw(ruby::rb_proc_call_with_block(
+            self.raw(),
+            args.len() as c_int,
+            args.as_ptr() as *const ruby::VALUE,
+            crate::util::NIL_VALUE,
+        ))
+    }
+
+    /// Calls `self` with `args` and returns its output, or an exception if one
+    /// is raised.
+    ///
+    /// # Safety
+    ///
+    /// Code executed from `self` may void the type safety of objects accessible
+    /// from Rust. For example, if one calls `push` on an `Array<A>` with an
+    /// object of type `B`, then the inserted object will be treated as being of
+    /// type `A`.
+    #[inline]
+    pub unsafe fn invoke_protected(self, args: &[impl Object]) -> Result<AnyObject> {
+        // monomorphization
+        unsafe fn invoke_protected(p: Proc, args: &[AnyObject]) -> Result<AnyObject> {
+            crate::protected_no_panic(|| p.invoke(args))
+        }
+        invoke_protected(self, AnyObject::convert_slice(args))
+    }
+
+    /// Returns the number of mandatory arguments taken by `self`.
+    ///
+    /// If `self` takes a variable number of arguments, the result is `-n - 1`,
+    /// where `n` is the number of mandatory arguments.
+    #[inline]
+    pub fn arity(self) -> i32 {
+        unsafe { ruby::rb_proc_arity(self.raw()) as i32 }
+    }
+
+    /// Returns whether `self` is a lambda, which has strict arity checking.
+    #[inline]
+    pub fn is_lambda(self) -> bool {
+        unsafe { ruby::rb_proc_lambda_p(self.raw()) == crate::util::TRUE_VALUE }
+    }
+}
+
+type RawProcFn = dyn FnMut(&[AnyObject]) -> AnyObject;
+
+// The Rust closure behind a `Proc` created via `Proc::new`
+struct ProcFn(RefCell<Box<RawProcFn>>);
+
+unsafe impl Rosy for ProcFn {
+    const ID: *const c_char = b"rust_proc\0".as_ptr() as _;
+
+    #[inline]
+    fn mark(&self) {}
+}
+
+#[inline]
+pub(crate) unsafe fn _args<'a>(argc: c_int, argv: *const ruby::VALUE) -> &'a [AnyObject] {
+    if argc <= 0 || argv.is_null() {
+        &[]
+    } else {
+        slice::from_raw_parts(argv as *const AnyObject, argc as usize)
+    }
+}
+
+pub(crate) type RawBlockFn<'a> = dyn FnMut(&[AnyObject]) -> AnyObject + 'a;
+
+// Calls `method` on `object` with `f` as the block, which only lives as long as
+// the call itself and thus is passed by reference
+//
+// The caller must ensure that `method` doesn't keep the block, since it would
+// otherwise be left with a dangling pointer to `f`
+pub(crate) unsafe fn _call_with_block(
+    object: AnyObject,
+    method: SymbolId,
+    args: &[AnyObject],
+    f: &mut RawBlockFn,
+) -> AnyObject {
+    let f = RefCell::new(f);
+    AnyObject::from_raw(ruby::rb_block_call(
+        object.raw(),
+        method.raw(),
+        args.len() as c_int,
+        args.as_ptr() as *const ruby::VALUE,
+        Some(call_block_fn),
+        &f as *const RefCell<&mut RawBlockFn> as ruby::VALUE,
+    ))
+}
+
+unsafe extern "C" fn call_block_fn(
+    _yielded_arg: ruby::VALUE,
+    callback_arg: ruby::VALUE,
+    argc: c_int,
+    argv: *const ruby::VALUE,
+    _block_arg: ruby::VALUE,
+) -> ruby::VALUE {
+    let f = &*(callback_arg as *const RefCell<&mut RawBlockFn>);
+    let args = _args(argc, argv);
+
+    let result = match f.try_borrow_mut() {
+        Ok(mut f) => crate::protected::_catch_jump(|| f(args)),
+        Err(_) => {
+            let message = "Rust closure called recursively";
+            RuntimeError::new(message).raise()
+        },
+    };
+    match result {
+        Ok(value) => value.raw(),
+        Err(jump) => jump.resume(),
+    }
+}
+
+unsafe extern "C" fn call_proc_fn(
+    _yielded_arg: ruby::VALUE,
+    callback_arg: ruby::VALUE,
+    argc: c_int,
+    argv: *const ruby::VALUE,
+    _block_arg: ruby::VALUE,
+) -> ruby::VALUE {
+    let data = RosyObject::<ProcFn>::from_raw(callback_arg);
+    let args = _args(argc, argv);
+
+    let result = match data.as_data().0.try_borrow_mut() {
+        Ok(mut f) => crate::protected::_catch_jump(|| f(args)),
+        Err(_) => {
+            let message = "Rust closure called recursively";
+            RuntimeError::new(message).raise()
+        },
+    };
+    match result {
+        Ok(value) => value.raw(),
+        Err(jump) => jump.resume(),
+    }
+}
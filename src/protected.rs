@@ -1,12 +1,17 @@
 use std::{
-    mem::{self, ManuallyDrop},
+    any::Any,
+    mem::{self, ManuallyDrop, MaybeUninit},
+    os::raw::c_int,
     panic,
     ptr,
+    sync::Once,
     thread::Result,
 };
 use crate::{
+    exception::Fatal,
     AnyException,
     AnyObject,
+    Exception,
     Object,
     ruby,
 };
@@ -68,6 +73,7 @@ pub fn protected<F, O>(f: F) -> crate::Result<O>
         let mut ctx = (Some(f), &mut *out);
         let ctx = &mut ctx as *mut (Option<F>, &mut _) as ruby::VALUE;
 
+        let borrows = crate::object::_borrow_count();
         let mut err = 1;
         ruby::rb_protect(Some(wrapper::<F, O>), ctx, &mut err);
         match err {
@@ -75,7 +81,10 @@ pub fn protected<F, O>(f: F) -> crate::Result<O>
                 Ok(out) => Ok(out),
                 Err(panic_info) => panic::resume_unwind(panic_info),
             },
-            _ => Err(AnyException::_take_current()),
+            _ => {
+                crate::object::_release_borrows(borrows);
+                Err(AnyException::_take_current())
+            },
         }
     }
 }
@@ -118,11 +127,15 @@ pub unsafe fn protected_no_panic<F, O>(f: F) -> crate::Result<O>
     let mut ctx = (Some(f), &mut *out);
     let ctx = &mut ctx as *mut (Option<F>, &mut O) as ruby::VALUE;
 
+    let borrows = crate::object::_borrow_count();
     let mut err = 0;
     ruby::rb_protect(Some(wrapper::<F, O>), ctx, &mut err);
     match err {
         0 => Ok(ManuallyDrop::into_inner(out)),
-        _ => Err(AnyException::_take_current()),
+        _ => {
+            crate::object::_release_borrows(borrows);
+            Err(AnyException::_take_current())
+        },
     }
 }
 
@@ -149,11 +162,122 @@ unsafe fn protected_no_panic_size_opt<F, O>(f: F) -> crate::Result<O>
     let mut ctx = Some(f);
     let ctx = &mut ctx as *mut Option<F> as ruby::VALUE;
 
+    let borrows = crate::object::_borrow_count();
     let mut err = 0;
     let val = ruby::rb_protect(Some(wrapper::<F, O>), ctx, &mut err);
     match err {
         0 => Ok(ptr::read(&val as *const ruby::VALUE as *const O)),
-        _ => Err(AnyException::_take_current()),
+        _ => {
+            crate::object::_release_borrows(borrows);
+            Err(AnyException::_take_current())
+        },
+    }
+}
+
+// A panic that was turned into an exception in order to unwind through Ruby's
+// stack frames. It's resumed once its exception is taken back on the Rust side.
+//
+// Only a single slot is needed since the GVL prevents more than one thread from
+// running Ruby code at a time.
+static mut PANIC: Option<Box<dyn Any + Send>> = None;
+static mut PANIC_EXCEPTION: ruby::VALUE = crate::util::NIL_VALUE;
+
+/// A non-local exit from Rust code that was called by Ruby.
+pub(crate) enum Jump {
+    /// A Ruby exception or some other jump (e.g. `throw`) with its tag state.
+    Tag(c_int),
+    /// A Rust panic with its payload.
+    Panic(Box<dyn Any + Send>),
+}
+
+impl Jump {
+    /// Returns whether `self` is a raised exception, as opposed to a `break`,
+    /// `throw` or panic.
+    #[inline]
+    pub(crate) fn is_raise(&self) -> bool {
+        match *self {
+            Jump::Tag(state) => state == ruby::TAG_RAISE,
+            Jump::Panic(_) => false,
+        }
+    }
+
+    /// Continues the jump through the Ruby stack frames above the caller.
+    ///
+    /// A panic is raised as a `fatal` exception that gets turned back into the
+    /// original panic when it reaches `protected` or any other protected call.
+    #[cold]
+    pub(crate) unsafe fn resume(self) -> ! {
+        match self {
+            Jump::Tag(state) => ruby::rb_jump_tag(state),
+            Jump::Panic(payload) => {
+                static REGISTER: Once = Once::new();
+                REGISTER.call_once(|| {
+                    ruby::rb_gc_register_address(ptr::addr_of_mut!(PANIC_EXCEPTION));
+                });
+
+                let message = if let Some(s) = payload.downcast_ref::<&str>() {
+                    *s
+                } else if let Some(s) = payload.downcast_ref::<std::string::String>() {
+                    s.as_str()
+                } else {
+                    "Box<Any>"
+                };
+                let exception = Fatal::new(format!("Rust panic: {}", message).as_str());
+
+                PANIC_EXCEPTION = exception.raw();
+                PANIC = Some(payload);
+
+                exception.raise()
+            },
+        }
+    }
+}
+
+/// Calls `f` on behalf of Ruby, catching any exception, jump, or panic that
+/// would otherwise escape from it.
+///
+/// Values that need to be dropped should be kept outside of `f` and dropped
+/// before calling `Jump::resume`.
+pub(crate) fn _catch_jump<F, O>(f: F) -> std::result::Result<O, Jump>
+    where F: FnOnce() -> O
+{
+    unsafe extern "C" fn wrapper<F, O>(ctx: ruby::VALUE) -> ruby::VALUE
+        where F: FnOnce() -> O
+    {
+        let (f, out) = &mut *(ctx as *mut (Option<F>, *mut Result<O>));
+        let f = f.take().unwrap_or_else(|| std::hint::unreachable_unchecked());
+
+        ptr::write(*out, panic::catch_unwind(panic::AssertUnwindSafe(f)));
+
+        AnyObject::nil().raw()
+    }
+    unsafe {
+        let mut out = MaybeUninit::<Result<O>>::uninit();
+        let mut ctx = (Some(f), out.as_mut_ptr());
+        let ctx = &mut ctx as *mut (Option<F>, *mut _) as ruby::VALUE;
+
+        let borrows = crate::object::_borrow_count();
+        let mut state = 0;
+        ruby::rb_protect(Some(wrapper::<F, O>), ctx, &mut state);
+        match state {
+            0 => out.assume_init().map_err(Jump::Panic),
+            _ => {
+                // The jump skipped the destructors of any `Ref` or `RefMut`
+                crate::object::_release_borrows(borrows);
+                Err(Jump::Tag(state))
+            },
+        }
+    }
+}
+
+// Resumes the panic that was raised as `exception` via `Jump::resume`, if any
+#[inline]
+pub(crate) unsafe fn _resume_panic(exception: ruby::VALUE) {
+    if exception == PANIC_EXCEPTION {
+        PANIC_EXCEPTION = crate::util::NIL_VALUE;
+        if let Some(payload) = (*ptr::addr_of_mut!(PANIC)).take() {
+            panic::resume_unwind(payload);
+        }
     }
 }
 
@@ -97,6 +97,14 @@ impl<S: Object, E: Object> fmt::Debug for Range<S, E> {
 }
 
 unsafe impl<S: Object, E: Object> Object for Range<S, E> {
+    #[inline]
+    fn cast<A: Object>(obj: A) -> Option<Self> {
+        if obj.class().inherits(Class::range()) {
+            unsafe { Some(Self::cast_unchecked(obj)) }
+        } else {
+            None
+        }
+    }
 }
 
 impl<S: Object, E: Object> IntoBounds<S, E> for Range<S, E> {
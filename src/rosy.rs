@@ -41,9 +41,62 @@ use crate::prelude::*;
 ///
 /// assert!(did_free);
 /// ```
+///
+/// # Deriving
+///
+/// With the `macros` feature enabled, this trait can be implemented via
+/// `#[derive(Rosy)]`. This generates:
+///
+/// - An [`ID`](#associatedconstant.ID) from the type's module path and name.
+///
+/// - A [`unique_object_id`](#method.unique_object_id) hashed from the type's
+///   path.
+///
+/// - A [`cast`](#method.cast) that also recognizes instances created from Ruby
+///   by `ID`.
+///
+/// - A [`mark`](#tymethod.mark) that marks each field via
+///   [`gc::Mark`](gc/trait.Mark.html). This is implemented for Rosy's object
+///   types and for standard library containers of them, like `Vec<Array>` or
+///   `Option<Box<String>>`.
+///
+/// - A [`size`](#method.size) that adds the
+///   [`gc::HeapSize`](gc/trait.HeapSize.html) of each field to the size of
+///   `Self`.
+///
+/// A field marked with `#[rosy(skip)]` is neither marked nor counted. The type
+/// can be given a class via `#[rosy(class = "Foo::Bar")]`, which defines the
+/// class along with any modules it's nested in if they don't exist.
+///
+/// Types with type, lifetime or const parameters are not supported, since
+/// every instance of them would share the same `ID`.
+///
+#[cfg_attr(feature = "macros", doc = "```")]
+#[cfg_attr(not(feature = "macros"), doc = "```ignore")]
+/// # rosy::vm::init().unwrap();
+/// use rosy::prelude::*;
+///
+/// #[derive(Rosy)]
+/// #[rosy(class = "Geometry::Shape")]
+/// enum Shape {
+///     Circle { radius: f64 },
+///     Polygon(Vec<(f64, f64)>, Option<String>),
+/// }
+///
+/// let shape = RosyObject::from(Shape::Circle { radius: 1.0 });
+/// let class = shape.class().into_any_class();
+///
+/// assert_eq!(class.name(), String::from("Geometry::Shape"));
+/// assert!(RosyObject::<Shape>::cast(shape).is_some());
+/// ```
 pub unsafe trait Rosy: Sized {
     /// A C string of the unique identifier of the type.
     ///
+    /// Instances of `Self` created by Ruby are recognized by this when casting
+    /// via [`RosyObject::cast_by_id`](struct.RosyObject.html#method.cast_by_id)
+    /// and when calling methods defined via `Class::def_initialize` and
+    /// similar. Those should only be used if no other type shares this value.
+    ///
     /// Note that the type is not
     /// [`CStr`](https://doc.rust-lang.org/std/ffi/struct.CStr.html). This is
     /// because creating a constant instance can only be done on nightly.
@@ -78,15 +131,16 @@ pub unsafe trait Rosy: Sized {
     ///
     /// The default implementation checks the
     /// [`unique_object_id`](#method.unique_object_id) of `Self` against the
-    /// `unique_id` of `A`.
+    /// `unique_id` of `A`, which fails if either is `None`.
+    ///
+    /// As a result, the default never recognizes instances created from Ruby,
+    /// such as via
+    /// [`Class::def_initialize`](struct.Class.html#method.def_initialize). A
+    /// type whose `ID` is unique should override this to call
+    /// [`RosyObject::cast_by_id`](struct.RosyObject.html#method.cast_by_id).
     #[inline]
-    #[allow(unused_variables)]
     fn cast<A: Object>(obj: A) -> Option<RosyObject<Self>> {
-        if A::unique_id() == Self::unique_object_id() {
-            unsafe { Some(RosyObject::cast_unchecked(obj)) }
-        } else {
-            None
-        }
+        _cast_same_type(obj)
     }
 
     /// Called during Ruby's mark phase of garbage collection to determine which
@@ -116,9 +170,43 @@ pub unsafe trait Rosy: Sized {
     }
 }
 
+/// A [`Rosy`](trait.Rosy.html) type whose instances can be created from Ruby
+/// by calling `new` on its class.
+///
+/// See [`Class::def_initialize`](struct.Class.html#method.def_initialize).
+pub trait Initialize: Rosy {
+    /// Returns the data of a new instance from the arguments passed to `new`.
+    fn initialize(args: &[AnyObject]) -> Result<Self>;
+}
+
+// Whether `A` is statically known to be `RosyObject<R>`
+#[inline]
+pub(crate) fn _is_same_type<R: Rosy, A: Object>() -> bool {
+    match (A::unique_id(), R::unique_object_id()) {
+        (Some(a), Some(r)) => a == r,
+        _ => false,
+    }
+}
+
+// Instances of generic types share the same `ID` and so can't be told apart
+// once created
+#[inline]
+fn _cast_same_type<R: Rosy, A: Object>(obj: A) -> Option<RosyObject<R>> {
+    if _is_same_type::<R, A>() {
+        unsafe { Some(RosyObject::cast_unchecked(obj)) }
+    } else {
+        None
+    }
+}
+
 unsafe impl<R: Rosy> Rosy for &[R] {
     const ID: *const c_char = b"rust_slice\0".as_ptr() as _;
 
+    #[inline]
+    fn cast<A: Object>(obj: A) -> Option<RosyObject<Self>> {
+        _cast_same_type(obj)
+    }
+
     #[inline]
     fn mark(&self) {
         self.iter().for_each(Rosy::mark);
@@ -133,6 +221,11 @@ unsafe impl<R: Rosy> Rosy for &[R] {
 unsafe impl<R: Rosy> Rosy for &mut [R] {
     const ID: *const c_char = b"rust_mut_slice\0".as_ptr() as _;
 
+    #[inline]
+    fn cast<A: Object>(obj: A) -> Option<RosyObject<Self>> {
+        _cast_same_type(obj)
+    }
+
     #[inline]
     fn mark(&self) {
         self.iter().for_each(Rosy::mark);
@@ -154,6 +247,11 @@ unsafe impl<R: Rosy> Rosy for Vec<R> {
         Some(inner.rotate_right(1) ^ base)
     }
 
+    #[inline]
+    fn cast<A: Object>(obj: A) -> Option<RosyObject<Self>> {
+        _cast_same_type(obj)
+    }
+
     #[inline]
     fn mark(&self) {
         self.iter().for_each(Rosy::mark);
@@ -193,3 +291,56 @@ unsafe impl Rosy for std::string::String {
         self.as_str().size()
     }
 }
+
+// 128-bit FNV-1a, which is used by `#[derive(Rosy)]` to turn a type path into
+// a `unique_object_id`
+#[doc(hidden)]
+pub fn _unique_object_id(path: &str) -> u128 {
+    const OFFSET: u128 = 0x6c62272e_07bb0142_62b82175_6295c58d;
+    const PRIME: u128 = 0x00000000_01000000_00000000_0000013b;
+
+    path.bytes().fold(OFFSET, |hash, byte| {
+        (hash ^ u128::from(byte)).wrapping_mul(PRIME)
+    })
+}
+
+// Returns the class at `path` (e.g. "Foo::Bar"), defining it along with any
+// missing modules it's nested in; used by `#[rosy(class = "...")]`
+#[doc(hidden)]
+pub fn _class_at_path(path: &str) -> Class {
+    use crate::mixin::DefMixinError;
+
+    #[cold]
+    fn fail(path: &str, error: DefMixinError) -> ! {
+        panic!("could not define class `{}`: {:?}", path, error);
+    }
+
+    let (namespace, name) = match path.rfind("::") {
+        Some(index) => (&path[..index], &path[(index + 2)..]),
+        None => ("", path),
+    };
+
+    let mut outer: std::result::Result<Class, Module> = Ok(Class::object());
+    for segment in namespace.split("::").filter(|s| !s.is_empty()) {
+        let module = match outer {
+            Ok(class) => class.def_module(segment),
+            Err(module) => module.def_module(segment),
+        };
+        outer = match module {
+            Ok(module) => Err(module),
+            Err(DefMixinError::ExistingClass(class)) => Ok(class),
+            Err(DefMixinError::ExistingModule(module)) => Err(module),
+            Err(error) => fail(path, error),
+        };
+    }
+
+    let class = match outer {
+        Ok(class) => class.def_class(name),
+        Err(module) => module.def_class(name),
+    };
+    match class {
+        Ok(class) => class,
+        Err(DefMixinError::ExistingClass(class)) => class,
+        Err(error) => fail(path, error),
+    }
+}
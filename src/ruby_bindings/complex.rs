@@ -0,0 +1,6 @@
+use super::prelude::*;
+
+extern "C" {
+    // VALUE rb_complex_new(VALUE real, VALUE imag)
+    pub fn rb_complex_new(real: VALUE, imag: VALUE) -> VALUE;
+}
@@ -48,4 +48,10 @@ pub struct rb_data_type_t_function {
 extern "C" {
     // VALUE rb_data_typed_object_wrap(VALUE klass, void *datap, const rb_data_type_t *type)
     pub fn rb_data_typed_object_wrap(klass: VALUE, datap: *mut c_void, ty: *const rb_data_type_t) -> VALUE;
+
+    // void rb_define_alloc_func(VALUE klass, rb_alloc_func_t func)
+    pub fn rb_define_alloc_func(klass: VALUE, func: Option<unsafe extern "C" fn(VALUE) -> VALUE>);
+
+    // void rb_undef_alloc_func(VALUE klass)
+    pub fn rb_undef_alloc_func(klass: VALUE);
 }
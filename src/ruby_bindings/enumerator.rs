@@ -0,0 +1,6 @@
+use super::prelude::*;
+
+extern "C" {
+    // VALUE rb_enumeratorize(VALUE obj, VALUE meth, int argc, const VALUE *argv)
+    pub fn rb_enumeratorize(obj: VALUE, meth: VALUE, argc: c_int, argv: *const VALUE) -> VALUE;
+}
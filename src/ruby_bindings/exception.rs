@@ -1,5 +1,8 @@
 use super::prelude::*;
 
+// RUBY_TAG_RAISE in `enum ruby_tag_type`, the state of a raised exception
+pub const TAG_RAISE: c_int = 0x6;
+
 extern "C" {
     // VALUE rb_errinfo(void)
     pub fn rb_errinfo() -> VALUE;
@@ -8,6 +11,8 @@ extern "C" {
 
     // NORETURN(void rb_exc_raise(VALUE mesg))
     pub fn rb_exc_raise(mesg: VALUE) -> !;
+    // NORETURN(void rb_jump_tag(int state))
+    pub fn rb_jump_tag(state: c_int) -> !;
     // VALUE rb_protect(VALUE (* proc) (VALUE), VALUE data, int *pstate)
     pub fn rb_protect(
         proc: Option<unsafe extern "C" fn(VALUE) -> VALUE>,
@@ -5,4 +5,6 @@ extern "C" {
     pub fn rb_float_new(d: f64) -> VALUE;
     // double rb_float_value(VALUE v)
     pub fn rb_float_value(v: VALUE) -> f64;
+    // double rb_num2dbl(VALUE val)
+    pub fn rb_num2dbl(val: VALUE) -> f64;
 }
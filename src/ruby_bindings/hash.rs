@@ -11,6 +11,12 @@ extern "C" {
     pub fn rb_hash_clear(hash: VALUE) -> VALUE;
     // VALUE rb_hash_delete(VALUE hash, VALUE key)
     pub fn rb_hash_delete(hash: VALUE, key: VALUE) -> VALUE;
+    // void rb_hash_foreach(VALUE hash, int (*func)(ANYARGS), VALUE farg)
+    pub fn rb_hash_foreach(
+        hash: VALUE,
+        func: Option<unsafe extern "C" fn(key: VALUE, val: VALUE, arg: VALUE) -> c_int>,
+        farg: VALUE,
+    );
     // VALUE rb_hash_new(void)
     pub fn rb_hash_new() -> VALUE;
     // size_t rb_hash_size_num(VALUE hash)
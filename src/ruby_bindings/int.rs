@@ -26,6 +26,9 @@ extern "C" {
     // VALUE rb_int2inum(intptr_t n)
     pub fn rb_int2inum(n: isize) -> VALUE;
 
+    // VALUE rb_str_to_inum(VALUE str, int base, int badcheck)
+    pub fn rb_str_to_inum(str: VALUE, base: c_int, badcheck: c_int) -> VALUE;
+
     // double rb_big2dbl(VALUE x)
     pub fn rb_big2dbl(x: VALUE) -> f64;
     // VALUE rb_big2str(VALUE x, int base)
@@ -71,6 +74,26 @@ extern "C" {
         nlz_bits_ret: *mut usize,
     ) -> usize;
 
+    // VALUE rb_int2big(intptr_t n)
+    pub fn rb_int2big(n: isize) -> VALUE;
+
+    // VALUE rb_big_plus(VALUE x, VALUE y);
+    pub fn rb_big_plus(x: VALUE, y: VALUE) -> VALUE;
+    // VALUE rb_big_minus(VALUE x, VALUE y);
+    pub fn rb_big_minus(x: VALUE, y: VALUE) -> VALUE;
+    // VALUE rb_big_mul(VALUE x, VALUE y);
+    pub fn rb_big_mul(x: VALUE, y: VALUE) -> VALUE;
+    // VALUE rb_big_div(VALUE x, VALUE y);
+    pub fn rb_big_div(x: VALUE, y: VALUE) -> VALUE;
+    // VALUE rb_big_modulo(VALUE x, VALUE y);
+    pub fn rb_big_modulo(x: VALUE, y: VALUE) -> VALUE;
+    // VALUE rb_big_pow(VALUE x, VALUE y);
+    pub fn rb_big_pow(x: VALUE, y: VALUE) -> VALUE;
+    // VALUE rb_big_lshift(VALUE x, VALUE y);
+    pub fn rb_big_lshift(x: VALUE, y: VALUE) -> VALUE;
+    // VALUE rb_big_rshift(VALUE x, VALUE y);
+    pub fn rb_big_rshift(x: VALUE, y: VALUE) -> VALUE;
+
     // VALUE rb_big_cmp(VALUE x, VALUE y);
     pub fn rb_big_cmp(x: VALUE, y: VALUE) -> VALUE;
     // VALUE rb_big_and(VALUE x, VALUE y);
@@ -102,6 +102,9 @@ extern "C" {
     pub fn rb_class_name(klass: VALUE) -> VALUE;
     // VALUE rb_class_new_instance(int argc, const VALUE *argv, VALUE klass)
     pub fn rb_class_new_instance(argc: c_int, argv: *const VALUE, klass: VALUE) -> VALUE;
+    // VALUE rb_class_new_instance_kw(int argc, const VALUE *argv, VALUE klass, int kw_splat)
+    #[cfg(feature = "ruby_2_7")]
+    pub fn rb_class_new_instance_kw(argc: c_int, argv: *const VALUE, klass: VALUE, kw_splat: c_int) -> VALUE;
     // VALUE rb_class_superclass(VALUE klass)
     pub fn rb_class_superclass(klass: VALUE) -> VALUE;
 
@@ -128,8 +131,36 @@ extern "C" {
         func: Option<unsafe extern "C" fn() -> VALUE>,
         argc: c_int,
     );
+    // void rb_define_module_function(VALUE module, const char *name, VALUE (*func)(ANYARGS), int argc)
+    pub fn rb_define_module_function(
+        module: VALUE,
+        name: *const c_char,
+        func: Option<unsafe extern "C" fn() -> VALUE>,
+        argc: c_int,
+    );
+
+    // void rb_define_private_method(VALUE klass, const char *name, VALUE (*func)(ANYARGS), int argc)
+    pub fn rb_define_private_method(
+        klass: VALUE,
+        name: *const c_char,
+        func: Option<unsafe extern "C" fn() -> VALUE>,
+        argc: c_int,
+    );
+    // void rb_define_protected_method(VALUE klass, const char *name, VALUE (*func)(ANYARGS), int argc)
+    pub fn rb_define_protected_method(
+        klass: VALUE,
+        name: *const c_char,
+        func: Option<unsafe extern "C" fn() -> VALUE>,
+        argc: c_int,
+    );
+
+    // void rb_alias(VALUE klass, ID alias_name, ID original_name)
+    pub fn rb_alias(klass: VALUE, alias_name: ID, original_name: ID);
+    // void rb_undef(VALUE klass, ID id)
+    pub fn rb_undef(klass: VALUE, id: ID);
+    // void rb_remove_method_id(VALUE klass, ID mid)
+    pub fn rb_remove_method_id(klass: VALUE, mid: ID);
 
-    // TODO: implement custom argument parsing rules
     // int rb_scan_args(int argc, const VALUE *argv, const char *fmt, ...)
     pub fn rb_scan_args(
         argc: c_int,
@@ -9,7 +9,9 @@ mod prelude {
 }
 
 mod array;
+mod complex;
 mod data;
+mod enumerator;
 mod exception;
 mod float;
 mod gc;
@@ -17,7 +19,9 @@ mod hash;
 mod int;
 mod mixin;
 mod object;
+mod proc;
 mod range;
+mod rational;
 mod string;
 mod symbol;
 mod vm;
@@ -113,7 +117,9 @@ pub mod fl_type {
 
 pub use self::{
     array::*,
+    complex::*,
     data::*,
+    enumerator::*,
     exception::*,
     float::*,
     gc::*,
@@ -121,7 +127,9 @@ pub use self::{
     int::*,
     mixin::*,
     object::*,
+    proc::*,
     range::*,
+    rational::*,
     string::*,
     symbol::*,
     vm::*,
@@ -24,6 +24,9 @@ extern "C" {
     pub fn rb_funcallv(recv: VALUE, mid: ID, argc: c_int, argv: *const VALUE) -> VALUE;
     // VALUE rb_funcallv_public(VALUE recv, ID mid, int argc, const VALUE *argv)
     pub fn rb_funcallv_public(recv: VALUE, mid: ID, argc: c_int, argv: *const VALUE) -> VALUE;
+    // VALUE rb_funcallv_kw(VALUE recv, ID mid, int argc, const VALUE *argv, int kw_splat)
+    #[cfg(feature = "ruby_2_7")]
+    pub fn rb_funcallv_kw(recv: VALUE, mid: ID, argc: c_int, argv: *const VALUE, kw_splat: c_int) -> VALUE;
 
     // VALUE rb_inspect(VALUE obj)
     pub fn rb_inspect(obj: VALUE) -> VALUE;
@@ -45,4 +48,9 @@ extern "C" {
 
     // VALUE rb_obj_instance_eval(int argc, const VALUE *argv, VALUE self)
     pub fn rb_obj_instance_eval(argc: c_int, argv: *const VALUE, this: VALUE) -> VALUE;
+
+    // VALUE rb_marshal_dump(VALUE obj, VALUE port)
+    pub fn rb_marshal_dump(obj: VALUE, port: VALUE) -> VALUE;
+    // VALUE rb_marshal_load(VALUE port)
+    pub fn rb_marshal_load(port: VALUE) -> VALUE;
 }
@@ -0,0 +1,47 @@
+use super::prelude::*;
+
+// typedef VALUE rb_block_call_func(RB_BLOCK_CALL_FUNC_ARGLIST(yielded_arg, callback_arg))
+#[allow(non_camel_case_types)]
+pub type rb_block_call_func_t = Option<unsafe extern "C" fn(
+    yielded_arg: VALUE,
+    callback_arg: VALUE,
+    argc: c_int,
+    argv: *const VALUE,
+    blockarg: VALUE,
+) -> VALUE>;
+
+extern "C" {
+    // int rb_block_given_p(void)
+    pub fn rb_block_given_p() -> c_int;
+    // VALUE rb_block_proc(void)
+    pub fn rb_block_proc() -> VALUE;
+    // VALUE rb_yield_values2(int n, const VALUE *argv)
+    pub fn rb_yield_values2(n: c_int, argv: *const VALUE) -> VALUE;
+    // VALUE rb_block_call(VALUE obj, ID mid, int argc, const VALUE *argv, rb_block_call_func_t bl_proc, VALUE data2)
+    pub fn rb_block_call(
+        obj: VALUE,
+        mid: ID,
+        argc: c_int,
+        argv: *const VALUE,
+        bl_proc: rb_block_call_func_t,
+        data2: VALUE,
+    ) -> VALUE;
+
+    // VALUE rb_proc_new(VALUE (*)(ANYARGS/* VALUE yieldarg[, VALUE procarg] */), VALUE)
+    pub fn rb_proc_new(func: rb_block_call_func_t, val: VALUE) -> VALUE;
+    // VALUE rb_obj_is_proc(VALUE proc)
+    pub fn rb_obj_is_proc(proc: VALUE) -> VALUE;
+
+    // VALUE rb_proc_call_with_block(VALUE self, int argc, const VALUE *argv, VALUE passed_proc)
+    pub fn rb_proc_call_with_block(
+        this: VALUE,
+        argc: c_int,
+        argv: *const VALUE,
+        passed_proc: VALUE,
+    ) -> VALUE;
+
+    // int rb_proc_arity(VALUE self)
+    pub fn rb_proc_arity(this: VALUE) -> c_int;
+    // VALUE rb_proc_lambda_p(VALUE self)
+    pub fn rb_proc_lambda_p(this: VALUE) -> VALUE;
+}
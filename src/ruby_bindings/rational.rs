@@ -0,0 +1,10 @@
+use super::prelude::*;
+
+extern "C" {
+    // VALUE rb_rational_new(VALUE num, VALUE den)
+    pub fn rb_rational_new(num: VALUE, den: VALUE) -> VALUE;
+    // VALUE rb_rational_num(VALUE rat)
+    pub fn rb_rational_num(rat: VALUE) -> VALUE;
+    // VALUE rb_rational_den(VALUE rat)
+    pub fn rb_rational_den(rat: VALUE) -> VALUE;
+}
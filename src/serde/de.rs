@@ -0,0 +1,464 @@
+use std::{cell::Cell, fmt};
+use serde::de::{self, IntoDeserializer, Visitor};
+use crate::prelude::*;
+
+/// A Serde `Deserializer` that reads Rust data from Ruby objects.
+///
+/// This is used by [`from_object`](fn.from_object.html).
+///
+/// Errors include the class of the object that couldn't be read and where it
+/// is within the tree of objects, such as `[0][:name]`.
+#[derive(Clone, Copy, Debug)]
+pub struct Deserializer<'a> {
+    object: AnyObject,
+    path: &'a Path<'a>,
+}
+
+// Where an object is within the tree of objects being read
+#[derive(Debug)]
+enum Path<'a> {
+    Root,
+    Index { parent: &'a Path<'a>, index: usize },
+    Key { parent: &'a Path<'a>, key: AnyObject },
+}
+
+impl fmt::Display for Path<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Path::Root => Ok(()),
+            Path::Index { parent, index } => write!(f, "{}[{}]", parent, index),
+            Path::Key { parent, key } => {
+                // `inspect` may be redefined to raise, in which case the class
+                // name is shown instead
+                let key = crate::protected(|| key.inspect())
+                    .unwrap_or_else(|_| key.class().name());
+                write!(f, "{}[{}]", parent, key)
+            },
+        }
+    }
+}
+
+// The last error returned by reading a child object, which already has context
+type ChildError = Cell<Option<AnyException>>;
+
+// Records `result` if it's an error from reading a child object
+#[inline]
+fn _child<T>(result: Result<T>, child_error: &ChildError) -> Result<T> {
+    if let Err(error) = result {
+        child_error.set(Some(error));
+    }
+    result
+}
+
+impl Deserializer<'static> {
+    /// Creates a new instance that reads from `object`.
+    #[inline]
+    pub fn new(object: impl Object) -> Self {
+        Deserializer { object: object.into(), path: &Path::Root }
+    }
+}
+
+impl Deserializer<'_> {
+    // Adds the class and path of `self.object` to an error that didn't come
+    // from reading a child object
+    fn _context<T>(
+        self,
+        result: Result<T>,
+        child_error: &ChildError,
+    ) -> Result<T> {
+        let error = match result {
+            Ok(value) => return Ok(value),
+            Err(error) => error,
+        };
+        if let Some(child_error) = child_error.get() {
+            if child_error.raw() == error.raw() {
+                return Err(error);
+            }
+        }
+        let class = self.object.class().name();
+        Err(match self.path {
+            Path::Root => de::Error::custom(format_args!(
+                "{}, found object of class `{}`",
+                error,
+                class,
+            )),
+            path => de::Error::custom(format_args!(
+                "{} at `{}`, found object of class `{}`",
+                error,
+                path,
+                class,
+            )),
+        })
+    }
+
+    // The error for when `self.object` isn't of a supported type
+    #[cold]
+    fn _unsupported(self) -> AnyException {
+        de::Error::custom("unsupported type")
+    }
+
+    // Reads a `String` or `Symbol` as a Rust string, or `None` if `self.object`
+    // is neither
+    fn _str(self) -> Option<Result<std::string::String>> {
+        let string = if let Some(string) = self.object.to_string() {
+            string
+        } else if let Some(symbol) = self.object.to_symbol() {
+            return Some(Ok(symbol.name().to_string_lossy().into()));
+        } else {
+            return None;
+        };
+        let result = unsafe { string.to_str() }
+            .map(Into::into)
+            .map_err(de::Error::custom);
+        Some(result)
+    }
+
+    // Reads `self.object` based on its type, recording errors from reading its
+    // children in `child_error`
+    fn _any<'de, V: Visitor<'de>>(
+        self,
+        visitor: V,
+        child_error: &ChildError,
+    ) -> Result<V::Value> {
+        let object = self.object;
+        if object.is_nil() {
+            visitor.visit_unit()
+        } else if let Some(b) = object.to_bool() {
+            visitor.visit_bool(b)
+        } else if let Some(int) = object.to_integer() {
+            if let Some(value) = int.to_value::<i64>() {
+                visitor.visit_i64(value)
+            } else if let Some(value) = int.to_value::<u64>() {
+                visitor.visit_u64(value)
+            } else if let Some(value) = int.to_value::<i128>() {
+                visitor.visit_i128(value)
+            } else if let Some(value) = int.to_value::<u128>() {
+                visitor.visit_u128(value)
+            } else {
+                Err(de::Error::custom(format_args!(
+                    "integer {} is too large",
+                    int,
+                )))
+            }
+        } else if let Some(float) = object.to_float() {
+            visitor.visit_f64(float.to_f64())
+        } else if let Some(string) = object.to_string() {
+            match unsafe { string.to_str() } {
+                Ok(s) => visitor.visit_str(s),
+                Err(_) => visitor.visit_bytes(unsafe { string.as_bytes() }),
+            }
+        } else if let Some(result) = self._str() {
+            visitor.visit_string(result?)
+        } else if let Some(array) = object.to_array() {
+            visitor.visit_seq(SeqAccess {
+                array,
+                index: 0,
+                path: self.path,
+                child_error,
+            })
+        } else if let Some(hash) = Hash::<AnyObject, AnyObject>::cast(object) {
+            let mut pairs = Vec::with_capacity(hash.len());
+            hash.for_each(|key, value| pairs.push((key, value)));
+            visitor.visit_map(MapAccess {
+                pairs: pairs.into_iter(),
+                value: None,
+                path: self.path,
+                child_error,
+            })
+        } else {
+            Err(self._unsupported())
+        }
+    }
+
+    // Reads an enum from a `Symbol`, `String` or single-entry `Hash`
+    fn _enum<'de, V: Visitor<'de>>(
+        self,
+        visitor: V,
+        child_error: &ChildError,
+    ) -> Result<V::Value> {
+        if let Some(variant) = self._str() {
+            return visitor.visit_enum(variant?.into_deserializer());
+        }
+        let hash = match Hash::<AnyObject, AnyObject>::cast(self.object) {
+            Some(hash) if hash.len() == 1 => hash,
+            _ => return Err(de::Error::custom(
+                "expected a `Symbol`, `String` or single-entry `Hash` for an \
+                 enum",
+            )),
+        };
+        let mut pair = None;
+        hash.for_each(|key, value| pair = Some((key, value)));
+        match pair {
+            Some((variant, value)) => visitor.visit_enum(EnumAccess {
+                variant,
+                value,
+                path: self.path,
+                child_error,
+            }),
+            None => Err(self._unsupported()),
+        }
+    }
+}
+
+macro_rules! forward_to_integer {
+    ($($method:ident => $visit:ident $t:ty;)+) => { $(
+        #[inline]
+        fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+            let result = match self.object.to_integer() {
+                Some(int) => match int.to_value::<$t>() {
+                    Some(value) => visitor.$visit(value),
+                    None => Err(de::Error::custom(format_args!(
+                        "integer {} out of range for `{}`",
+                        int,
+                        stringify!($t),
+                    ))),
+                },
+                None => return self.deserialize_any(visitor),
+            };
+            self._context(result, &ChildError::default())
+        }
+    )+ };
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer<'_> {
+    type Error = AnyException;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let child_error = ChildError::default();
+        let result = self._any(visitor, &child_error);
+        self._context(result, &child_error)
+    }
+
+    forward_to_integer! {
+        deserialize_i8   => visit_i8   i8;
+        deserialize_i16  => visit_i16  i16;
+        deserialize_i32  => visit_i32  i32;
+        deserialize_i64  => visit_i64  i64;
+        deserialize_i128 => visit_i128 i128;
+        deserialize_u8   => visit_u8   u8;
+        deserialize_u16  => visit_u16  u16;
+        deserialize_u32  => visit_u32  u32;
+        deserialize_u64  => visit_u64  u64;
+        deserialize_u128 => visit_u128 u128;
+    }
+
+    /// Reads an `Integer` as well as a `Float`.
+    #[inline]
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.object.to_integer() {
+            Some(int) => {
+                let result = visitor.visit_f64(int.to_f64());
+                self._context(result, &ChildError::default())
+            },
+            None => self.deserialize_any(visitor),
+        }
+    }
+
+    #[inline]
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_f64(visitor)
+    }
+
+    #[inline]
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.object.to_string() {
+            Some(string) => {
+                let result = visitor.visit_bytes(unsafe { string.as_bytes() });
+                self._context(result, &ChildError::default())
+            },
+            None => self.deserialize_any(visitor),
+        }
+    }
+
+    #[inline]
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_bytes(visitor)
+    }
+
+    #[inline]
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        if self.object.is_nil() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    #[inline]
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        let child_error = ChildError::default();
+        let result = self._enum(visitor, &child_error);
+        self._context(result, &child_error)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool char str string unit unit_struct seq tuple tuple_struct map
+        struct identifier ignored_any
+    }
+}
+
+// Reads the elements of an `Array`
+struct SeqAccess<'a> {
+    array: Array,
+    index: usize,
+    path: &'a Path<'a>,
+    child_error: &'a ChildError,
+}
+
+impl<'de> de::SeqAccess<'de> for SeqAccess<'_> {
+    type Error = AnyException;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>>
+        where T: de::DeserializeSeed<'de>
+    {
+        match self.array.get(self.index) {
+            Some(element) => {
+                let path = Path::Index { parent: self.path, index: self.index };
+                let element = Deserializer { object: element, path: &path };
+                self.index += 1;
+                _child(seed.deserialize(element), self.child_error).map(Some)
+            },
+            None => Ok(None),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.array.len().saturating_sub(self.index))
+    }
+}
+
+// Reads the pairs of a `Hash`, collected beforehand
+struct MapAccess<'a> {
+    pairs: std::vec::IntoIter<(AnyObject, AnyObject)>,
+    value: Option<(AnyObject, AnyObject)>,
+    path: &'a Path<'a>,
+    child_error: &'a ChildError,
+}
+
+impl<'de> de::MapAccess<'de> for MapAccess<'_> {
+    type Error = AnyException;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
+        where K: de::DeserializeSeed<'de>
+    {
+        match self.pairs.next() {
+            Some((key, value)) => {
+                self.value = Some((key, value));
+                let key = Deserializer { object: key, path: self.path };
+                _child(seed.deserialize(key), self.child_error).map(Some)
+            },
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value>
+        where V: de::DeserializeSeed<'de>
+    {
+        match self.value.take() {
+            Some((key, value)) => {
+                let path = Path::Key { parent: self.path, key };
+                let value = Deserializer { object: value, path: &path };
+                _child(seed.deserialize(value), self.child_error)
+            },
+            None => Err(de::Error::custom("value is missing")),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.pairs.len())
+    }
+}
+
+// Reads the variant of an enum from a single-entry `Hash`
+struct EnumAccess<'a> {
+    variant: AnyObject,
+    value: AnyObject,
+    path: &'a Path<'a>,
+    child_error: &'a ChildError,
+}
+
+impl<'a, 'de> de::EnumAccess<'de> for EnumAccess<'a> {
+    type Error = AnyException;
+    type Variant = VariantAccess<'a>;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, VariantAccess<'a>)>
+        where V: de::DeserializeSeed<'de>
+    {
+        let variant = Deserializer { object: self.variant, path: self.path };
+        let variant = _child(seed.deserialize(variant), self.child_error)?;
+        Ok((variant, VariantAccess {
+            variant: self.variant,
+            value: self.value,
+            path: self.path,
+            child_error: self.child_error,
+        }))
+    }
+}
+
+struct VariantAccess<'a> {
+    variant: AnyObject,
+    value: AnyObject,
+    path: &'a Path<'a>,
+    child_error: &'a ChildError,
+}
+
+impl VariantAccess<'_> {
+    // Reads the value of the variant with `f`
+    #[inline]
+    fn _read<T, F>(self, f: F) -> Result<T>
+        where F: FnOnce(Deserializer) -> Result<T>
+    {
+        let path = Path::Key { parent: self.path, key: self.variant };
+        let value = Deserializer { object: self.value, path: &path };
+        _child(f(value), self.child_error)
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for VariantAccess<'_> {
+    type Error = AnyException;
+
+    #[inline]
+    fn unit_variant(self) -> Result {
+        self._read(|value| de::Deserialize::deserialize(value))
+    }
+
+    #[inline]
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value>
+        where T: de::DeserializeSeed<'de>
+    {
+        self._read(|value| seed.deserialize(value))
+    }
+
+    #[inline]
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value>
+        where V: Visitor<'de>
+    {
+        self._read(|value| de::Deserializer::deserialize_seq(value, visitor))
+    }
+
+    #[inline]
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+        where V: Visitor<'de>
+    {
+        self._read(|value| de::Deserializer::deserialize_map(value, visitor))
+    }
+}
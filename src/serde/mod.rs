@@ -0,0 +1,128 @@
+//! Converting between Rust data and Ruby objects via [Serde].
+//!
+//! **Requires feature:** `serde`
+//!
+//! Rust data is represented in Ruby as follows:
+//!
+//! | Rust                       | Ruby                                     |
+//! | -------------------------- | ---------------------------------------- |
+//! | `bool`                     | `true` or `false`                        |
+//! | integers                   | `Integer`                                |
+//! | `f32` and `f64`            | `Float`                                  |
+//! | `char` and strings         | `String`                                 |
+//! | bytes                      | `String` with binary encoding            |
+//! | `None` and `()`            | `nil`                                    |
+//! | sequences and tuples       | `Array`                                  |
+//! | maps                       | `Hash`                                   |
+//! | structs                    | `Hash` with `Symbol` keys                |
+//! | unit variants              | `Symbol`                                 |
+//! | other variants             | `Hash` with a single `Symbol` key        |
+//!
+//! When reading objects back, a `Symbol` can be read wherever a string is
+//! expected and vice versa.
+//!
+//! # Examples
+//!
+//! ```
+//! # rosy::vm::init().unwrap();
+//! use serde::{Deserialize, Serialize};
+//! use rosy::prelude::*;
+//!
+//! #[derive(Serialize, Deserialize, Debug, PartialEq)]
+//! struct Config {
+//!     name: std::string::String,
+//!     retries: u32,
+//!     tags: Vec<std::string::String>,
+//! }
+//!
+//! let config = Config {
+//!     name: "server".into(),
+//!     retries: 3,
+//!     tags: vec!["fast".into()],
+//! };
+//!
+//! let object = rosy::serde::to_object(&config).unwrap();
+//! let hash = Hash::<Symbol, AnyObject>::cast(object).unwrap();
+//! assert_eq!(hash.get("retries").unwrap(), 3);
+//!
+//! let back: Config = rosy::serde::from_object(object).unwrap();
+//! assert_eq!(back, config);
+//! ```
+//!
+//! Errors are returned as `TypeError` exceptions. When an object can't be read
+//! back, its class and where it is within `object` are included in the
+//! message:
+//!
+//! ```
+//! # rosy::vm::init().unwrap();
+//! use rosy::prelude::*;
+//!
+//! let error = rosy::serde::from_object::<u32>(Class::object())
+//!     .unwrap_err();
+//!
+//! assert!(error.is_type_error());
+//! assert!(error.to_string().contains("Class"));
+//!
+//! let array = Array::from_slice(&[String::from("x")]);
+//! let error = rosy::serde::from_object::<Vec<u32>>(array).unwrap_err();
+//!
+//! assert_eq!(
+//!     error.to_string(),
+//!     "invalid type: string \"x\", expected u32 at `[0]`, \
+//!      found object of class `String`",
+//! );
+//! ```
+//!
+//! [Serde]: https://serde.rs
+
+use std::fmt;
+use crate::{
+    exception::TypeError,
+    prelude::*,
+};
+
+mod de;
+mod ser;
+
+#[doc(inline)]
+pub use self::{
+    de::Deserializer,
+    ser::{SerializeArray, SerializeHash, SerializeVariant, Serializer},
+};
+
+/// Converts `value` into a tree of Ruby objects.
+#[inline]
+pub fn to_object<T>(value: &T) -> Result<AnyObject>
+    where T: ?Sized + ::serde::Serialize
+{
+    value.serialize(Serializer)
+}
+
+/// Reads an instance of `T` from the tree of Ruby objects at `object`.
+///
+/// Strings in `T` are copied out of `object` and so `T` may not borrow from it.
+#[inline]
+pub fn from_object<T>(object: impl Object) -> Result<T>
+    where T: ::serde::de::DeserializeOwned
+{
+    T::deserialize(Deserializer::new(object))
+}
+
+impl ::serde::ser::Error for AnyException {
+    #[cold]
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        _error(msg)
+    }
+}
+
+impl ::serde::de::Error for AnyException {
+    #[cold]
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        _error(msg)
+    }
+}
+
+#[cold]
+fn _error(msg: impl fmt::Display) -> AnyException {
+    TypeError::new(msg.to_string().as_str()).into_any_exception()
+}
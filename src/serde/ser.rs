@@ -0,0 +1,391 @@
+use serde::ser::{self, Serialize};
+use crate::prelude::*;
+
+/// A Serde `Serializer` that converts Rust data into Ruby objects.
+///
+/// This is used by [`to_object`](fn.to_object.html).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Serializer;
+
+impl ser::Serializer for Serializer {
+    type Ok = AnyObject;
+    type Error = AnyException;
+
+    type SerializeSeq = SerializeArray;
+    type SerializeTuple = SerializeArray;
+    type SerializeTupleStruct = SerializeArray;
+    type SerializeTupleVariant = SerializeVariant<SerializeArray>;
+    type SerializeMap = SerializeHash;
+    type SerializeStruct = SerializeHash;
+    type SerializeStructVariant = SerializeVariant<SerializeHash>;
+
+    #[inline]
+    fn serialize_bool(self, v: bool) -> Result<AnyObject> {
+        Ok(AnyObject::from_bool(v))
+    }
+
+    #[inline]
+    fn serialize_i8(self, v: i8) -> Result<AnyObject> {
+        Ok(Integer::from(v).into())
+    }
+
+    #[inline]
+    fn serialize_i16(self, v: i16) -> Result<AnyObject> {
+        Ok(Integer::from(v).into())
+    }
+
+    #[inline]
+    fn serialize_i32(self, v: i32) -> Result<AnyObject> {
+        Ok(Integer::from(v).into())
+    }
+
+    #[inline]
+    fn serialize_i64(self, v: i64) -> Result<AnyObject> {
+        Ok(Integer::from(v).into())
+    }
+
+    #[inline]
+    fn serialize_i128(self, v: i128) -> Result<AnyObject> {
+        Ok(Integer::from(v).into())
+    }
+
+    #[inline]
+    fn serialize_u8(self, v: u8) -> Result<AnyObject> {
+        Ok(Integer::from(v).into())
+    }
+
+    #[inline]
+    fn serialize_u16(self, v: u16) -> Result<AnyObject> {
+        Ok(Integer::from(v).into())
+    }
+
+    #[inline]
+    fn serialize_u32(self, v: u32) -> Result<AnyObject> {
+        Ok(Integer::from(v).into())
+    }
+
+    #[inline]
+    fn serialize_u64(self, v: u64) -> Result<AnyObject> {
+        Ok(Integer::from(v).into())
+    }
+
+    #[inline]
+    fn serialize_u128(self, v: u128) -> Result<AnyObject> {
+        Ok(Integer::from(v).into())
+    }
+
+    #[inline]
+    fn serialize_f32(self, v: f32) -> Result<AnyObject> {
+        self.serialize_f64(v.into())
+    }
+
+    #[inline]
+    fn serialize_f64(self, v: f64) -> Result<AnyObject> {
+        Ok(Float::from(v).into())
+    }
+
+    #[inline]
+    fn serialize_char(self, v: char) -> Result<AnyObject> {
+        self.serialize_str(v.encode_utf8(&mut [0; 4]))
+    }
+
+    #[inline]
+    fn serialize_str(self, v: &str) -> Result<AnyObject> {
+        Ok(String::from(v).into())
+    }
+
+    #[inline]
+    fn serialize_bytes(self, v: &[u8]) -> Result<AnyObject> {
+        Ok(String::from(v).into())
+    }
+
+    #[inline]
+    fn serialize_none(self) -> Result<AnyObject> {
+        Ok(AnyObject::nil())
+    }
+
+    #[inline]
+    fn serialize_some<T>(self, value: &T) -> Result<AnyObject>
+        where T: ?Sized + Serialize
+    {
+        value.serialize(self)
+    }
+
+    #[inline]
+    fn serialize_unit(self) -> Result<AnyObject> {
+        Ok(AnyObject::nil())
+    }
+
+    #[inline]
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<AnyObject> {
+        self.serialize_unit()
+    }
+
+    #[inline]
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<AnyObject> {
+        Ok(Symbol::from(variant).into())
+    }
+
+    #[inline]
+    fn serialize_newtype_struct<T>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<AnyObject>
+        where T: ?Sized + Serialize
+    {
+        value.serialize(self)
+    }
+
+    #[inline]
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<AnyObject>
+        where T: ?Sized + Serialize
+    {
+        let value = value.serialize(self)?;
+        Ok(_variant_hash(variant, value).into())
+    }
+
+    #[inline]
+    fn serialize_seq(self, len: Option<usize>) -> Result<SerializeArray> {
+        let array = match len {
+            Some(len) => Array::with_capacity(len),
+            None => Array::new(),
+        };
+        Ok(SerializeArray { array })
+    }
+
+    #[inline]
+    fn serialize_tuple(self, len: usize) -> Result<SerializeArray> {
+        self.serialize_seq(Some(len))
+    }
+
+    #[inline]
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeArray> {
+        self.serialize_seq(Some(len))
+    }
+
+    #[inline]
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeVariant<SerializeArray>> {
+        let inner = self.serialize_seq(Some(len))?;
+        Ok(SerializeVariant { variant, inner })
+    }
+
+    #[inline]
+    fn serialize_map(self, _len: Option<usize>) -> Result<SerializeHash> {
+        Ok(SerializeHash { hash: Hash::new(), key: None })
+    }
+
+    #[inline]
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeHash> {
+        self.serialize_map(Some(len))
+    }
+
+    #[inline]
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeVariant<SerializeHash>> {
+        let inner = self.serialize_map(Some(len))?;
+        Ok(SerializeVariant { variant, inner })
+    }
+}
+
+// Creates `{ variant: value }` for a non-unit enum variant
+fn _variant_hash(variant: &str, value: AnyObject) -> Hash {
+    let hash = Hash::new();
+    // Safe because `Symbol#hash` can't be redefined to do anything nefarious
+    unsafe { hash.insert(Symbol::from(variant), value) };
+    hash
+}
+
+/// Serializes sequences and tuples into an `Array`.
+#[derive(Debug)]
+pub struct SerializeArray {
+    array: Array,
+}
+
+impl ser::SerializeSeq for SerializeArray {
+    type Ok = AnyObject;
+    type Error = AnyException;
+
+    #[inline]
+    fn serialize_element<T>(&mut self, value: &T) -> Result
+        where T: ?Sized + Serialize
+    {
+        let value = value.serialize(Serializer)?;
+        unsafe { self.array.push(value) };
+        Ok(())
+    }
+
+    #[inline]
+    fn end(self) -> Result<AnyObject> {
+        Ok(self.array.into())
+    }
+}
+
+impl ser::SerializeTuple for SerializeArray {
+    type Ok = AnyObject;
+    type Error = AnyException;
+
+    #[inline]
+    fn serialize_element<T>(&mut self, value: &T) -> Result
+        where T: ?Sized + Serialize
+    {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<AnyObject> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeArray {
+    type Ok = AnyObject;
+    type Error = AnyException;
+
+    #[inline]
+    fn serialize_field<T>(&mut self, value: &T) -> Result
+        where T: ?Sized + Serialize
+    {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<AnyObject> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+/// Serializes maps and structs into a `Hash`.
+#[derive(Debug)]
+pub struct SerializeHash {
+    hash: Hash,
+    key: Option<AnyObject>,
+}
+
+impl ser::SerializeMap for SerializeHash {
+    type Ok = AnyObject;
+    type Error = AnyException;
+
+    #[inline]
+    fn serialize_key<T>(&mut self, key: &T) -> Result
+        where T: ?Sized + Serialize
+    {
+        self.key = Some(key.serialize(Serializer)?);
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_value<T>(&mut self, value: &T) -> Result
+        where T: ?Sized + Serialize
+    {
+        let key = match self.key.take() {
+            Some(key) => key,
+            None => {
+                let message = "`serialize_value` called before `serialize_key`";
+                return Err(ser::Error::custom(message));
+            },
+        };
+        let value = value.serialize(Serializer)?;
+        // Safe because keys can only be built-in types with a sound `#hash`
+        unsafe { self.hash.insert(key, value) };
+        Ok(())
+    }
+
+    #[inline]
+    fn end(self) -> Result<AnyObject> {
+        Ok(self.hash.into())
+    }
+}
+
+impl ser::SerializeStruct for SerializeHash {
+    type Ok = AnyObject;
+    type Error = AnyException;
+
+    #[inline]
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result
+        where T: ?Sized + Serialize
+    {
+        let value = value.serialize(Serializer)?;
+        unsafe { self.hash.insert(Symbol::from(key), value) };
+        Ok(())
+    }
+
+    #[inline]
+    fn end(self) -> Result<AnyObject> {
+        ser::SerializeMap::end(self)
+    }
+}
+
+/// Serializes a tuple or struct enum variant into a `Hash` with the variant
+/// name as its single key.
+#[derive(Debug)]
+pub struct SerializeVariant<S> {
+    variant: &'static str,
+    inner: S,
+}
+
+impl ser::SerializeTupleVariant for SerializeVariant<SerializeArray> {
+    type Ok = AnyObject;
+    type Error = AnyException;
+
+    #[inline]
+    fn serialize_field<T>(&mut self, value: &T) -> Result
+        where T: ?Sized + Serialize
+    {
+        ser::SerializeSeq::serialize_element(&mut self.inner, value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<AnyObject> {
+        let value = ser::SerializeSeq::end(self.inner)?;
+        Ok(_variant_hash(self.variant, value).into())
+    }
+}
+
+impl ser::SerializeStructVariant for SerializeVariant<SerializeHash> {
+    type Ok = AnyObject;
+    type Error = AnyException;
+
+    #[inline]
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result
+        where T: ?Sized + Serialize
+    {
+        ser::SerializeStruct::serialize_field(&mut self.inner, key, value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<AnyObject> {
+        let value = ser::SerializeMap::end(self.inner)?;
+        Ok(_variant_hash(self.variant, value).into())
+    }
+}
@@ -296,6 +296,19 @@ macro_rules! common_ids {
 
 common_ids! {
     equal_op            => "==",
+    plus_op             => "+",
+    minus_op            => "-",
+    mul_op              => "*",
+    div_op              => "/",
+    rem_op              => "%",
+    uminus_op           => "-@",
+    real                => "real",
+    imaginary           => "imaginary",
+    abs                 => "abs",
+    coerce              => "coerce",
+    finite_q            => "finite?",
+    integer_q           => "integer?",
+    zero_q              => "zero?",
     backtrace           => "backtrace",
     cause               => "cause",
     size                => "size",
@@ -0,0 +1,315 @@
+//! Owned snapshots of Ruby data.
+
+use std::{
+    ffi::CString,
+    os::raw::c_int,
+    ptr,
+};
+use crate::{
+    num::pack::Options,
+    object::Kind,
+    prelude::*,
+    ruby,
+    string::Encoding,
+};
+
+/// An owned Rust representation of a tree of Ruby objects.
+///
+/// This is created via [`AnyObject::to_value`], which deeply copies the
+/// contents of an object. Other than [`Other`](#variant.Other), the result
+/// stays valid after the garbage collector runs or the VM is destroyed.
+///
+/// Converting back into an `AnyObject` creates new objects with the same
+/// contents.
+///
+/// # Cycles
+///
+/// An `Array` or `Hash` that contains itself, either directly or through
+/// another `Array` or `Hash`, is converted with [`Cycle`](#variant.Cycle) at
+/// the place of the repeated object. Converting back recreates the cycle.
+///
+/// ```
+/// # rosy::vm::init().unwrap();
+/// use rosy::{prelude::*, Value};
+///
+/// let array = Array::<AnyObject>::new();
+/// unsafe {
+///     array.push(Integer::from(1).into());
+///     array.push(array.into());
+/// }
+///
+/// let value = array.into_any_object().to_value();
+/// assert_eq!(value, Value::Array(vec![Value::from(1), Value::Cycle(0)]));
+///
+/// let copy = Array::<AnyObject>::cast(AnyObject::from(value)).unwrap();
+/// assert_eq!(copy.get(1).unwrap(), copy);
+/// ```
+///
+/// [`AnyObject::to_value`]: ../struct.AnyObject.html#method.to_value
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    /// `nil`.
+    Nil,
+    /// `true` or `false`.
+    Bool(bool),
+    /// An `Integer` of any size.
+    Integer(IntegerValue),
+    /// A `Float`.
+    Float(f64),
+    /// A `String`, along with its encoding.
+    String(StringValue),
+    /// The name of a `Symbol`.
+    Symbol(std::string::String),
+    /// The elements of an `Array`.
+    Array(Vec<Value>),
+    /// The key-value pairs of a `Hash`, in insertion order.
+    Hash(Vec<(Value, Value)>),
+    /// A `Range`.
+    Range {
+        /// The start bound.
+        start: Box<Value>,
+        /// The end bound, which is `Nil` for an endless range.
+        end: Box<Value>,
+        /// Whether `end` is excluded from the range.
+        exclusive: bool,
+    },
+    /// A reference to an `Array` or `Hash` that contains this value, where `0`
+    /// refers to the innermost one.
+    Cycle(usize),
+    /// An object of any other type, which is not copied.
+    ///
+    /// Like any other `AnyObject`, this is only valid while the object is
+    /// reachable by the garbage collector.
+    Other(AnyObject),
+}
+
+/// The value of an `Integer` of any size.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum IntegerValue {
+    /// A value that fits in an `i128`.
+    Small(i128),
+    /// A value too large for `i128`.
+    Big {
+        /// Whether the value is less than 0.
+        negative: bool,
+        /// The magnitude of the value, least-significant word first.
+        words: Vec<u64>,
+    },
+}
+
+/// The contents of a `String`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct StringValue {
+    /// The raw bytes.
+    pub bytes: Vec<u8>,
+    /// The name of the encoding of `bytes`, such as `UTF-8`.
+    pub encoding: std::string::String,
+}
+
+impl StringValue {
+    /// Returns the bytes as a string slice if they're valid UTF-8.
+    #[inline]
+    pub fn to_str(&self) -> Option<&str> {
+        std::str::from_utf8(&self.bytes).ok()
+    }
+}
+
+impl From<&str> for StringValue {
+    #[inline]
+    fn from(s: &str) -> Self {
+        StringValue { bytes: s.into(), encoding: "UTF-8".into() }
+    }
+}
+
+impl From<Integer> for IntegerValue {
+    fn from(int: Integer) -> Self {
+        if let Some(value) = int.to_value::<i128>() {
+            return IntegerValue::Small(value);
+        }
+        let len = unsafe {
+            ruby::rb_absint_numwords(int.raw(), 64, ptr::null_mut())
+        };
+        let mut words = vec![0u64; len];
+        let sign = int.pack(&mut words);
+        IntegerValue::Big { negative: sign.is_negative(), words }
+    }
+}
+
+impl From<IntegerValue> for Integer {
+    #[inline]
+    fn from(value: IntegerValue) -> Self {
+        match value {
+            IntegerValue::Small(value) => Integer::from(value),
+            IntegerValue::Big { negative, words } => {
+                let options = Options::default();
+                let options = if negative { options.is_negative() } else { options };
+                Integer::unpack_using(&words, options)
+            },
+        }
+    }
+}
+
+impl From<bool> for Value {
+    #[inline]
+    fn from(b: bool) -> Self {
+        Value::Bool(b)
+    }
+}
+
+macro_rules! impl_from_int {
+    ($($t:ty)+) => { $(
+        impl From<$t> for Value {
+            #[inline]
+            fn from(int: $t) -> Self {
+                Value::Integer(IntegerValue::Small(int.into()))
+            }
+        }
+    )+ };
+}
+
+impl_from_int! { u64 u32 u16 u8 i128 i64 i32 i16 i8 }
+
+impl From<f64> for Value {
+    #[inline]
+    fn from(f: f64) -> Self {
+        Value::Float(f)
+    }
+}
+
+impl From<&str> for Value {
+    #[inline]
+    fn from(s: &str) -> Self {
+        Value::String(s.into())
+    }
+}
+
+impl From<Value> for AnyObject {
+    /// Creates new objects with the contents of `value`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bounds of a `Range` can't be compared.
+    #[inline]
+    fn from(value: Value) -> Self {
+        value._into_object(&mut Vec::new())
+    }
+}
+
+impl Value {
+    // Converts `obj`, where `parents` are the arrays and hashes that contain it
+    pub(crate) fn _from_object(
+        obj: AnyObject,
+        parents: &mut Vec<AnyObject>,
+    ) -> Value {
+        // Compares identities, since `==` may be redefined or recurse
+        let raw = obj.raw();
+        if let Some(index) = parents.iter().rposition(|p| p.raw() == raw) {
+            return Value::Cycle(parents.len() - 1 - index);
+        }
+        match obj.kind() {
+            Kind::Nil => Value::Nil,
+            Kind::True => Value::Bool(true),
+            Kind::False => Value::Bool(false),
+            Kind::Fixnum(int) |
+            Kind::Bignum(int) => Value::Integer(int.into()),
+            Kind::Float(float) => Value::Float(float.to_f64()),
+            Kind::String(string) => {
+                let bytes = string.to_bytes();
+                let encoding = string.encoding().name().to_string_lossy().into();
+                Value::String(StringValue { bytes, encoding })
+            },
+            Kind::Symbol(symbol) => {
+                Value::Symbol(symbol.name().to_string_lossy().into())
+            },
+            Kind::Array(array) => {
+                parents.push(obj);
+                let values = array.into_iter()
+                    .map(|obj| Value::_from_object(obj, parents))
+                    .collect();
+                parents.pop();
+                Value::Array(values)
+            },
+            Kind::Hash(hash) => {
+                parents.push(obj);
+                let mut pairs = Vec::with_capacity(hash.len());
+                hash.for_each(|key, val| pairs.push((
+                    Value::_from_object(key, parents),
+                    Value::_from_object(val, parents),
+                )));
+                parents.pop();
+                Value::Hash(pairs)
+            },
+            Kind::Range(range) => {
+                let mut start: ruby::VALUE = 0;
+                let mut end: ruby::VALUE = 0;
+                let mut excl: c_int = 0;
+                unsafe {
+                    ruby::rb_range_values(range.raw(), &mut start, &mut end, &mut excl);
+                    Value::Range {
+                        start: Box::new(Value::_from_object(AnyObject::from_raw(start), parents)),
+                        end: Box::new(Value::_from_object(AnyObject::from_raw(end), parents)),
+                        exclusive: excl != 0,
+                    }
+                }
+            },
+            _ => Value::Other(obj),
+        }
+    }
+
+    // Creates objects for `self`, where `parents` are the arrays and hashes
+    // that will contain it
+    fn _into_object(self, parents: &mut Vec<AnyObject>) -> AnyObject {
+        match self {
+            Value::Nil => AnyObject::nil(),
+            Value::Bool(b) => b.into(),
+            Value::Integer(int) => Integer::from(int).into(),
+            Value::Float(f) => f.into(),
+            Value::String(StringValue { bytes, encoding }) => {
+                let string = String::from(&bytes[..]);
+                let encoding = CString::new(encoding).ok()
+                    .and_then(|name| Encoding::find(&name).ok());
+                if let Some(encoding) = encoding {
+                    // Safe because `string` isn't referenced anywhere else
+                    unsafe { string.force_encoding(encoding) };
+                }
+                string.into()
+            },
+            Value::Symbol(name) => Symbol::from(name.as_str()).into(),
+            Value::Array(values) => {
+                let array = Array::<AnyObject>::with_capacity(values.len());
+                parents.push(array.into());
+                for value in values {
+                    let obj = value._into_object(parents);
+                    unsafe { array.push(obj) };
+                }
+                parents.pop();
+                array.into()
+            },
+            Value::Hash(pairs) => {
+                let hash = Hash::<AnyObject, AnyObject>::new();
+                parents.push(hash.into());
+                for (key, val) in pairs {
+                    let key = key._into_object(parents);
+                    let val = val._into_object(parents);
+                    unsafe { hash.insert(key, val) };
+                }
+                parents.pop();
+                hash.into()
+            },
+            Value::Range { start, end, exclusive } => {
+                let start = start._into_object(parents);
+                let end = end._into_object(parents);
+                Range::from_bounds(start, end, exclusive)
+                    .expect("Range bounds can't be compared")
+                    .into()
+            },
+            Value::Cycle(n) => {
+                match parents.len().checked_sub(n + 1) {
+                    Some(index) => parents[index],
+                    None => AnyObject::nil(),
+                }
+            },
+            Value::Other(obj) => obj,
+        }
+    }
+}